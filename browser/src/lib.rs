@@ -1,8 +1,8 @@
 use std::sync::OnceLock;
 
 use ratatui::Terminal as RatatTerm;
-use ratatui_wrapper::Terminal;
-use resume_tui::{App, Event};
+use ratatui_wrapper::{writeToTerminal, Terminal};
+use resume_tui::{clipboard, hyperlink::Registry, Action, App, Event, DATABASE};
 use wasm_bindgen::prelude::*;
 
 mod ratatui_wrapper;
@@ -10,21 +10,46 @@ mod ratatui_wrapper;
 static mut TERMINAL: OnceLock<RatatTerm<Terminal>> = OnceLock::new();
 static mut APP: OnceLock<App> = OnceLock::new();
 
+/// Initialize the app, if `route` is provided (e.g. from the URL fragment) the
+/// app will start on that page.
 #[wasm_bindgen]
-pub fn run() -> Result<(), JsValue> {
+pub fn run(route: Option<String>) -> Result<(), JsValue> {
     console_error_panic_hook::set_once();
     log::set_boxed_logger(Box::new(Log)).unwrap();
     log::set_max_level(log::LevelFilter::Trace);
-    let terminal = ratatui::Terminal::new(ratatui_wrapper::Terminal).unwrap();
+    // xterm.js supports OSC 8, index.html opens the links in a new tab
+    let hyperlinks = Registry::new(true);
+    let terminal = ratatui::Terminal::new(ratatui_wrapper::Terminal(hyperlinks.clone())).unwrap();
     unsafe {
         TERMINAL
             .set(terminal)
             .unwrap_or_else(|_| panic!("couldn't set terminal"));
-        APP.set(resume_tui::App::new()).unwrap();
+        APP.set(App::with_hyperlinks(&DATABASE, hyperlinks))
+            .unwrap();
+    }
+    if let Some(route) = route.filter(|r| !r.is_empty()) {
+        open(route)?;
     }
     Ok(())
 }
 
+/// Navigate to the page described by `route`
+#[wasm_bindgen]
+pub fn open(route: String) -> Result<(), JsValue> {
+    let Some(app) = (unsafe { APP.get_mut() }) else {
+        web_sys::console::log_1(&JsValue::from_str("no APP!"));
+        return Ok(());
+    };
+    let Some(t) = (unsafe { TERMINAL.get_mut() }) else {
+        web_sys::console::log_1(&JsValue::from_str("no TERMINAL!"));
+        return Ok(());
+    };
+    app.open(&route)
+        .map_err(|e| JsValue::from_str(&format!("RouteError: {e}")))?;
+    app.tick(t)
+        .map_err(|e| JsValue::from_str(&format!("TickError: {e}")))
+}
+
 #[wasm_bindgen]
 pub fn event(event: u8) -> Result<(), JsValue> {
     web_sys::console::log_1(&JsValue::from_str(&format!("event {event}")));
@@ -37,6 +62,7 @@ pub fn event(event: u8) -> Result<(), JsValue> {
         return Ok(());
     };
     handle_event(event as _, app)?;
+    handle_action(app);
     web_sys::console::log_1(&JsValue::from_str("ticking!"));
     app.tick(t)
         .map_err(|e| JsValue::from_str(&format!("TickError: {e}")))
@@ -49,6 +75,10 @@ fn handle_event(event: u8, app: &mut App) -> Result<(), JsValue> {
         2 => Event::Down,
         3 => Event::Left,
         4 => Event::Right,
+        5 => Event::Tab,
+        6 => Event::Yank,
+        7 => Event::NextHeading,
+        8 => Event::PrevHeading,
         _ => {
             return Ok(());
         }
@@ -58,6 +88,25 @@ fn handle_event(event: u8, app: &mut App) -> Result<(), JsValue> {
     Ok(())
 }
 
+/// Links can't be opened from inside the terminal so they are copied to the
+/// clipboard with OSC 52 like yanked text, index.html handles the escape
+/// sequence
+fn handle_action(app: &mut App) {
+    let Some(action) = app.take_action() else {
+        return;
+    };
+    match action {
+        Action::Open(url) => {
+            writeToTerminal(clipboard::osc52(&url));
+            app.set_status(format!(" Copied {url} to the clipboard "));
+        }
+        Action::Copy(text) => {
+            writeToTerminal(clipboard::osc52(&text));
+            app.set_status(" Copied to the clipboard ");
+        }
+    }
+}
+
 struct Log;
 
 impl log::Log for Log {
@@ -1,16 +1,19 @@
 use ratatui::buffer::Cell;
 use ratatui::layout::Size;
 use ratatui::style::{Color, Modifier};
+use resume_tui::hyperlink::{self, Registry};
 use wasm_bindgen::prelude::*;
 
-pub struct Terminal;
+/// Writes frames to xterm.js, the cells registered with the registry are
+/// written as hyperlinks
+pub struct Terminal(pub Registry);
 
 #[wasm_bindgen]
 extern "C" {
     #[wasm_bindgen]
     fn writeToCanvas(s: String, x: u16, y: u16, bold: bool, italic: bool);
     #[wasm_bindgen]
-    fn writeToTerminal(s: String);
+    pub fn writeToTerminal(s: String);
     #[wasm_bindgen]
     fn canvasSizeW() -> u16;
     #[wasm_bindgen]
@@ -25,7 +28,7 @@ impl ratatui::backend::Backend for Terminal {
         writeToTerminal(
             content
                 .into_iter()
-                .map(|(x, y, c)| ansiify_cell(x, y, c))
+                .map(|(x, y, c)| ansiify_cell(x, y, c, &self.0))
                 .collect(),
         );
         Ok(())
@@ -84,7 +87,7 @@ impl ratatui::backend::Backend for Terminal {
     }
 }
 
-fn ansiify_cell(x: u16, y: u16, cell: &Cell) -> String {
+fn ansiify_cell(x: u16, y: u16, cell: &Cell, hyperlinks: &Registry) -> String {
     const BOLD: &str = "\x1B[1m";
     const DIM: &str = "\x1B[2m";
     const UNDERLINE: &str = "\x1B[4m";
@@ -121,7 +124,11 @@ fn ansiify_cell(x: u16, y: u16, cell: &Cell) -> String {
         ret.push_str(REVERSED);
     }
 
-    ret.push_str(cell.symbol());
+    if let Some(url) = hyperlinks.url_at(x, y) {
+        ret.push_str(&hyperlink::wrap(cell.symbol(), &url));
+    } else {
+        ret.push_str(cell.symbol());
+    }
     ret.push_str("\x1B[0m");
     ret
 }
@@ -9,6 +9,11 @@ use quote::quote;
 use serde::Deserialize;
 use syn::{punctuated::Punctuated, LitStr, Token};
 
+// Shared with the library so dates are validated when the data is parsed
+#[path = "src/date.rs"]
+#[allow(dead_code)]
+mod date;
+
 fn main() {
     setup_logging();
     let out_dir = env::var_os("OUT_DIR").unwrap();
@@ -57,6 +62,7 @@ fn setup_logging() {
 
 fn emit_rebuild_directives(path: impl AsRef<Path>) {
     println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=RESUME_DATA_PATH");
     println!("cargo:rerun-if-changed={}", path.as_ref().display());
 }
 
@@ -66,8 +72,7 @@ fn generate_from_toml_files(path: PathBuf) -> String {
     log::debug!("{info:#?}");
     let name = LitStr::new(&info.name, Span::call_site());
     let tag_line = LitStr::new(&info.tag_line, Span::call_site());
-    let github = optional_str(info.github);
-    let linkedin = optional_str(info.linkedin);
+    let contact = info.contact();
     let jobs_text = std::fs::read_to_string(path.join("jobs.toml")).unwrap();
     let mut jobs_value: Jobs = toml::from_str(&jobs_text).unwrap();
     collect_jobs(&path, &mut jobs_value);
@@ -81,20 +86,125 @@ fn generate_from_toml_files(path: PathBuf) -> String {
     let edu_text = std::fs::read_to_string(path.join("edu.toml")).unwrap();
     let edu: Education = toml::from_str(&edu_text).unwrap();
     let edu = TokenStream::from(edu);
+    let publications: Publications = read_optional_toml(path.join("publications.toml"));
+    log::debug!("{publications:#?}");
+    let publications = publications.into_tokens(quote!(Publication));
+    let talks: Talks = read_optional_toml(path.join("talks.toml"));
+    log::debug!("{talks:#?}");
+    let talks = talks.into_tokens();
+    let certifications: Certifications = read_optional_toml(path.join("certifications.toml"));
+    log::debug!("{certifications:#?}");
+    let certifications = TokenStream::from(certifications);
+    let sections: Sections = read_optional_toml(path.join("sections.toml"));
+    log::debug!("{sections:#?}");
+    let sections = TokenStream::from(sections);
+    let build_date = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let build_date = date_tokens(date::Date::from_unix_secs(build_date));
+    let images = image_tokens(&path);
     quote::quote! {
-        pub static DATABASE: Database = Database {
+        /// The month this crate was built, used as the current date when the
+        /// system clock isn't available
+        pub const BUILD_DATE: Date = #build_date;
+
+        pub static DATABASE: Database<'static> = Database {
             name: #name,
             tag_line: #tag_line,
-            github: #github,
-            linkedin: #linkedin,
+            contact: #contact,
             jobs: #jobs,
             open_source: #oss,
             education: #edu,
+            publications: #publications,
+            talks: #talks,
+            certifications: #certifications,
+            sections: #sections,
         };
+
+        pub(crate) static IMAGES: &[Image] = #images;
     }
     .to_string()
 }
 
+/// The widest and tallest image art in pixels, larger images are shrunk to
+/// fit
+#[cfg(feature = "image-art")]
+const IMAGE_ART_MAX: (u32, u32) = (40, 80);
+
+/// Character art for every image under the data directory. Decoding images
+/// slows down the build so this is behind the `image-art` feature
+#[cfg(feature = "image-art")]
+fn image_tokens(base_path: &Path) -> TokenStream {
+    fn visit(base_path: &Path, dir: &Path, images: &mut Vec<TokenStream>) {
+        let mut entries: Vec<_> = fs::read_dir(dir)
+            .unwrap()
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .collect();
+        entries.sort();
+        for path in entries {
+            if path.is_dir() {
+                visit(base_path, &path, images);
+                continue;
+            }
+            let is_image = path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| {
+                    matches!(ext.to_lowercase().as_str(), "png" | "jpg" | "jpeg" | "gif")
+                });
+            if !is_image {
+                continue;
+            }
+            let decoded = match image::open(&path) {
+                Ok(decoded) => decoded,
+                Err(e) => {
+                    println!("cargo:warning=skipping {}: {e}", path.display());
+                    continue;
+                }
+            };
+            let (max_width, max_height) = IMAGE_ART_MAX;
+            let decoded = if decoded.width() > max_width || decoded.height() > max_height {
+                decoded.resize(max_width, max_height, image::imageops::FilterType::Triangle)
+            } else {
+                decoded
+            };
+            let decoded = decoded.to_rgba8();
+            let pixels = decoded.pixels().map(|image::Rgba([r, g, b, a])| {
+                if *a < 128 {
+                    quote!(None)
+                } else {
+                    quote!(Some([#r, #g, #b]))
+                }
+            });
+            let relative = path.strip_prefix(base_path).unwrap_or(&path);
+            let relative = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            let relative = LitStr::new(&relative, Span::call_site());
+            let width = decoded.width() as usize;
+            images.push(quote! {
+                Image {
+                    path: #relative,
+                    width: #width,
+                    pixels: &[#(#pixels),*],
+                }
+            });
+        }
+    }
+    let mut images = Vec::new();
+    visit(base_path, base_path, &mut images);
+    quote!(&[#(#images),*])
+}
+
+#[cfg(not(feature = "image-art"))]
+fn image_tokens(_base_path: &Path) -> TokenStream {
+    quote!(&[])
+}
+
 fn collect_jobs(base_path: impl AsRef<Path>, jobs: &mut Jobs) {
     for job in jobs.jobs.iter_mut() {
         let job_dir = job.id.as_ref().unwrap_or(&job.company);
@@ -131,15 +241,34 @@ fn collect_details(maybe_job_path: impl AsRef<Path>, job: &mut Job) {
                 id
             });
 
-        job.details.push(detail);
+        let details = match &detail.role {
+            Some(id) => {
+                let role = job.roles.iter_mut().find(|r| r.id.as_ref() == Some(id));
+                let role = role.unwrap_or_else(|| {
+                    panic!(
+                        "{}: {} has an unknown role id `{id}`",
+                        job.company,
+                        file.path().display()
+                    )
+                });
+                &mut role.details
+            }
+            None => &mut job.details,
+        };
+        details.push(detail);
+    }
+    let details = Some(&mut job.details)
+        .into_iter()
+        .chain(job.roles.iter_mut().map(|r| &mut r.details));
+    for details in details {
+        details.sort_by(|l, r| {
+            let id = l.id.cmp(&r.id);
+            if matches!(id, Ordering::Equal) {
+                return l.headline.cmp(&r.headline);
+            }
+            id
+        });
     }
-    job.details.sort_by(|l, r| {
-        let id = l.id.cmp(&r.id);
-        if matches!(id, Ordering::Equal) {
-            return l.headline.cmp(&r.headline);
-        }
-        id
-    });
 }
 
 fn collect_oss(base_path: impl AsRef<Path>, projects: &mut Projects) {
@@ -169,9 +298,85 @@ fn collect_oss(base_path: impl AsRef<Path>, projects: &mut Projects) {
 pub struct Info {
     name: String,
     tag_line: String,
+    #[serde(default)]
+    email: Option<String>,
+    #[serde(default)]
+    website: Option<String>,
+    #[serde(default)]
+    phone: Option<String>,
+    #[serde(default)]
+    location: Option<String>,
+    #[serde(default, alias = "profile")]
+    profiles: Vec<Profile>,
+    /// Shorthand for a GitHub profile
+    #[serde(default)]
     github: Option<String>,
+    /// Shorthand for a LinkedIn profile
+    #[serde(default)]
     linkedin: Option<String>,
 }
+
+impl Info {
+    fn contact(&self) -> TokenStream {
+        let email = optional_str(self.email.clone());
+        let website = optional_str(self.website.clone());
+        let phone = optional_str(self.phone.clone());
+        let location = optional_str(self.location.clone());
+        let shorthand = [("GitHub", &self.github), ("LinkedIn", &self.linkedin)]
+            .into_iter()
+            .filter_map(|(network, username)| {
+                Some(Profile {
+                    network: network.to_string(),
+                    username: username.clone()?,
+                    url: None,
+                })
+            });
+        let profiles: Punctuated<TokenStream, Token![,]> = self
+            .profiles
+            .iter()
+            .cloned()
+            .chain(shorthand)
+            .map(TokenStream::from)
+            .collect();
+        quote! {
+            Contact {
+                email: #email,
+                website: #website,
+                phone: #phone,
+                location: #location,
+                profiles: &[#profiles],
+            }
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Profile {
+    network: String,
+    username: String,
+    #[serde(default)]
+    url: Option<String>,
+}
+
+impl From<Profile> for TokenStream {
+    fn from(value: Profile) -> Self {
+        let Profile {
+            network,
+            username,
+            url,
+        } = value;
+        let network = LitStr::new(&network, Span::call_site());
+        let username = LitStr::new(&username, Span::call_site());
+        let url = optional_str(url);
+        quote! {
+            Profile {
+                network: #network,
+                username: #username,
+                url: #url,
+            }
+        }
+    }
+}
 #[derive(Debug, Deserialize)]
 pub struct Jobs {
     #[serde(rename = "job")]
@@ -194,6 +399,26 @@ pub struct Job {
     #[serde(default)]
     id: Option<String>,
     company: String,
+    /// Defaults to the most recent role's title
+    #[serde(default)]
+    title: Option<String>,
+    /// Defaults to the earliest role's start
+    #[serde(default)]
+    start: Option<String>,
+    #[serde(default)]
+    end: Option<String>,
+    #[serde(default, alias = "detail")]
+    details: Vec<Detail>,
+    #[serde(default, alias = "role")]
+    roles: Vec<Role>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Role {
+    /// An optional unique ID used to attach details in the `job_details`
+    /// directory to this role
+    #[serde(default)]
+    id: Option<String>,
     title: String,
     start: String,
     #[serde(default)]
@@ -201,13 +426,20 @@ pub struct Job {
     #[serde(default, alias = "detail")]
     details: Vec<Detail>,
 }
+
 #[derive(Debug, Deserialize)]
 pub struct Detail {
     #[serde(default)]
     id: u32,
+    /// The id of the role a detail in the `job_details` directory belongs
+    /// to
+    #[serde(default)]
+    role: Option<String>,
     headline: String,
     snippet: String,
     detail: String,
+    #[serde(default)]
+    tags: Vec<String>,
 }
 
 impl From<Job> for TokenStream {
@@ -219,13 +451,39 @@ impl From<Job> for TokenStream {
             start,
             end,
             details: detail,
+            mut roles,
         } = value;
+        for (idx, role) in roles.iter().enumerate() {
+            let Some(id) = &role.id else {
+                continue;
+            };
+            if roles[..idx].iter().any(|r| r.id.as_ref() == Some(id)) {
+                panic!("{company}: more than one role has the id `{id}`");
+            }
+        }
+        roles.sort_by_key(|role| std::cmp::Reverse(parse_date(&role.start, &company)));
+        let title = title
+            .or_else(|| roles.first().map(|role| role.title.clone()))
+            .unwrap_or_else(|| panic!("{company}: a job needs a title or at least one role"));
+        let start = start
+            .map(|start| parse_date(&start, &company))
+            .or_else(|| roles.last().map(|role| parse_date(&role.start, &company)))
+            .unwrap_or_else(|| panic!("{company}: a job needs a start or at least one role"));
+        let start = date_tokens(start);
+        // without an explicit end the job ends with the most recent role
+        let end = match roles.first() {
+            Some(role) if end.is_none() => optional_date(role.end.clone(), &company),
+            _ => optional_date(end, &company),
+        };
+        let company_name = company.clone();
         let company = LitStr::new(&company, Span::call_site());
         let title = LitStr::new(&title, Span::call_site());
-        let start = LitStr::new(&start, Span::call_site());
-        let end = optional_str(end);
         let details: Punctuated<TokenStream, Token![,]> =
             detail.into_iter().map(TokenStream::from).collect();
+        let roles: Punctuated<TokenStream, Token![,]> = roles
+            .into_iter()
+            .map(|role| role.into_tokens(&company_name))
+            .collect();
         quote::quote! {
             Workplace {
                 name: #company,
@@ -233,6 +491,33 @@ impl From<Job> for TokenStream {
                 start: #start,
                 end: #end,
                 details: &[#details],
+                roles: &[#roles],
+            }
+        }
+    }
+}
+
+impl Role {
+    fn into_tokens(self, company: &str) -> TokenStream {
+        let Role {
+            id: _,
+            title,
+            start,
+            end,
+            details,
+        } = self;
+        let context = format!("{company} {title}");
+        let start = date_tokens(parse_date(&start, &context));
+        let end = optional_date(end, &context);
+        let title = LitStr::new(&title, Span::call_site());
+        let details: Punctuated<TokenStream, Token![,]> =
+            details.into_iter().map(TokenStream::from).collect();
+        quote::quote! {
+            Role {
+                title: #title,
+                start: #start,
+                end: #end,
+                details: &[#details],
             }
         }
     }
@@ -241,19 +526,24 @@ impl From<Job> for TokenStream {
 impl From<Detail> for TokenStream {
     fn from(value: Detail) -> Self {
         let Detail {
+            id,
+            role: _,
             headline,
             snippet,
             detail,
-            ..
+            tags,
         } = value;
         let headline = LitStr::new(&headline, Span::call_site());
         let snippet = LitStr::new(&snippet, Span::call_site());
         let detail = LitStr::new(&detail, Span::call_site());
+        let tags = tags.iter().map(|tag| LitStr::new(tag, Span::call_site()));
         quote::quote! {
             Detail {
+                id: #id,
                 headline: #headline,
                 snippet: #snippet,
                 detail: #detail,
+                tags: &[#(#tags),*],
             }
         }
     }
@@ -280,8 +570,12 @@ impl From<Project> for TokenStream {
             name,
             short_desc,
             sub_projects,
+            start,
+            end,
             id: _,
         } = value;
+        let start = optional_date(start, &name);
+        let end = optional_date(end, &name);
         let long_desc = LitStr::new(&long_desc, Span::call_site());
         let name = LitStr::new(&name, Span::call_site());
         let short_desc = LitStr::new(&short_desc, Span::call_site());
@@ -293,6 +587,8 @@ impl From<Project> for TokenStream {
                 name: #name,
                 short_desc: #short_desc,
                 long_desc: #long_desc,
+                start: #start,
+                end: #end,
                 sub_projects: &[#sub_projects],
             }
         }
@@ -306,6 +602,10 @@ pub struct Project {
     pub name: String,
     pub short_desc: String,
     pub long_desc: String,
+    #[serde(default)]
+    pub start: Option<String>,
+    #[serde(default)]
+    pub end: Option<String>,
     #[serde(default, alias = "sub_project")]
     pub sub_projects: Vec<Project>,
 }
@@ -314,16 +614,19 @@ impl From<School> for TokenStream {
     fn from(value: School) -> Self {
         let School {
             name,
+            start,
             graduated,
             desc,
         } = value;
+        let start = optional_date(start, &name);
+        let graduated = optional_date(graduated, &name);
         let name = LitStr::new(&name, Span::call_site());
-        let graduated = optional_str(graduated);
         let desc = LitStr::new(&desc, Span::call_site());
 
         quote! {
             School {
                 name: #name,
+                start: #start,
                 graduated: #graduated,
                 desc: #desc,
             }
@@ -347,10 +650,264 @@ pub struct Education {
 #[derive(Debug, Clone, Deserialize)]
 pub struct School {
     pub name: String,
+    #[serde(default)]
+    pub start: Option<String>,
     pub graduated: Option<String>,
     pub desc: String,
 }
 
+fn read_optional_toml<T: Default + serde::de::DeserializeOwned>(path: impl AsRef<Path>) -> T {
+    let path = path.as_ref();
+    if !path.exists() {
+        return T::default();
+    }
+    let text = std::fs::read_to_string(path).unwrap();
+    toml::from_str(&text).unwrap()
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Publications {
+    #[serde(default, alias = "publication")]
+    publications: Vec<Publication>,
+}
+
+impl Publications {
+    fn into_tokens(self, ty: TokenStream) -> TokenStream {
+        let publications: Punctuated<TokenStream, Token![,]> = self
+            .publications
+            .into_iter()
+            .map(|p| p.into_tokens(&ty))
+            .collect();
+        quote::quote!(&[#publications])
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Talks {
+    #[serde(default, alias = "talk")]
+    talks: Vec<Publication>,
+}
+
+impl Talks {
+    fn into_tokens(self) -> TokenStream {
+        Publications {
+            publications: self.talks,
+        }
+        .into_tokens(quote!(Talk))
+    }
+}
+
+/// Both publications and talks share the same fields
+#[derive(Debug, Clone, Deserialize)]
+pub struct Publication {
+    title: String,
+    venue: String,
+    date: String,
+    #[serde(default, alias = "co_presenters")]
+    co_authors: Vec<String>,
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default, rename = "abstract")]
+    summary: String,
+}
+
+impl Publication {
+    fn into_tokens(self, ty: &TokenStream) -> TokenStream {
+        let Publication {
+            title,
+            venue,
+            date,
+            co_authors,
+            url,
+            summary,
+        } = self;
+        let date = date_tokens(parse_date(&date, &title));
+        let title = LitStr::new(&title, Span::call_site());
+        let venue = LitStr::new(&venue, Span::call_site());
+        let co_authors: Punctuated<LitStr, Token![,]> = co_authors
+            .iter()
+            .map(|a| LitStr::new(a, Span::call_site()))
+            .collect();
+        let url = optional_str(url);
+        let summary = LitStr::new(&summary, Span::call_site());
+        quote! {
+            #ty {
+                title: #title,
+                venue: #venue,
+                date: #date,
+                co_authors: &[#co_authors],
+                url: #url,
+                summary: #summary,
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Certifications {
+    #[serde(default, alias = "certification")]
+    certifications: Vec<Certification>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Certification {
+    name: String,
+    issuer: String,
+    issued: String,
+    #[serde(default)]
+    expires: Option<String>,
+    #[serde(default)]
+    credential_id: Option<String>,
+    #[serde(default)]
+    url: Option<String>,
+}
+
+impl From<Certifications> for TokenStream {
+    fn from(value: Certifications) -> Self {
+        let certifications: Punctuated<TokenStream, Token![,]> = value
+            .certifications
+            .into_iter()
+            .map(|c| {
+                let Certification {
+                    name,
+                    issuer,
+                    issued,
+                    expires,
+                    credential_id,
+                    url,
+                } = c;
+                let issued = date_tokens(parse_date(&issued, &name));
+                let expires = optional_date(expires, &name);
+                let name = LitStr::new(&name, Span::call_site());
+                let issuer = LitStr::new(&issuer, Span::call_site());
+                let credential_id = optional_str(credential_id);
+                let url = optional_str(url);
+                quote! {
+                    Certification {
+                        name: #name,
+                        issuer: #issuer,
+                        issued: #issued,
+                        expires: #expires,
+                        credential_id: #credential_id,
+                        url: #url,
+                    }
+                }
+            })
+            .collect();
+        quote!(&[#certifications])
+    }
+}
+
+/// Parse a date, panicking with a message pointing to `context` if it is
+/// invalid
+fn parse_date(value: &str, context: &str) -> date::Date {
+    date::Date::parse(value).unwrap_or_else(|e| panic!("{context}: {e}"))
+}
+
+fn optional_date(value: Option<String>, context: &str) -> TokenStream {
+    value
+        .map(|value| {
+            let date = date_tokens(parse_date(&value, context));
+            quote!(Some(#date))
+        })
+        .unwrap_or_else(|| quote!(None))
+}
+
+fn date_tokens(value: date::Date) -> TokenStream {
+    let year = value.year;
+    let month = value
+        .month
+        .map(|m| quote!(Some(#m)))
+        .unwrap_or_else(|| quote!(None));
+    quote! {
+        Date {
+            year: #year,
+            month: #month,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Sections {
+    #[serde(default, alias = "section")]
+    sections: Vec<Section>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Section {
+    title: String,
+    #[serde(default, alias = "entry")]
+    entries: Vec<Entry>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Entry {
+    headline: String,
+    #[serde(default)]
+    snippet: String,
+    #[serde(default)]
+    body: String,
+    #[serde(default)]
+    start: Option<String>,
+    #[serde(default)]
+    end: Option<String>,
+    #[serde(default, alias = "child")]
+    children: Vec<Entry>,
+}
+
+impl From<Sections> for TokenStream {
+    fn from(value: Sections) -> Self {
+        let sections: Punctuated<TokenStream, Token![,]> =
+            value.sections.into_iter().map(TokenStream::from).collect();
+        quote::quote!(&[#sections])
+    }
+}
+
+impl From<Section> for TokenStream {
+    fn from(value: Section) -> Self {
+        let Section { title, entries } = value;
+        let title = LitStr::new(&title, Span::call_site());
+        let entries: Punctuated<TokenStream, Token![,]> =
+            entries.into_iter().map(TokenStream::from).collect();
+        quote! {
+            Section {
+                title: #title,
+                entries: &[#entries],
+            }
+        }
+    }
+}
+
+impl From<Entry> for TokenStream {
+    fn from(value: Entry) -> Self {
+        let Entry {
+            headline,
+            snippet,
+            body,
+            start,
+            end,
+            children,
+        } = value;
+        let start = optional_date(start, &headline);
+        let end = optional_date(end, &headline);
+        let headline = LitStr::new(&headline, Span::call_site());
+        let snippet = LitStr::new(&snippet, Span::call_site());
+        let body = LitStr::new(&body, Span::call_site());
+        let children: Punctuated<TokenStream, Token![,]> =
+            children.into_iter().map(TokenStream::from).collect();
+        quote! {
+            Entry {
+                headline: #headline,
+                snippet: #snippet,
+                body: #body,
+                start: #start,
+                end: #end,
+                children: &[#children],
+            }
+        }
+    }
+}
+
 fn optional_str(value: Option<String>) -> TokenStream {
     value
         .map(|s| {
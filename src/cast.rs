@@ -0,0 +1,209 @@
+//! Recording of terminal output in the [asciicast v2] format.
+//!
+//! [asciicast v2]: https://docs.asciinema.org/manual/asciicast/v2/
+
+use std::{
+    io::{self, Write},
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+/// A writer that passes everything through to `inner` while also recording
+/// each flushed chunk of output as an asciicast `"o"` event in `cast`.
+///
+/// Ratatui flushes the backend once per `Terminal::draw` so each event in the
+/// cast will be the ANSI output of a single frame. The recording is finished
+/// when the recorder is dropped.
+pub struct Recorder<T: Write, C: Write> {
+    inner: T,
+    cast: C,
+    pending: Vec<u8>,
+    resizes: Resizes,
+    clock: Box<dyn Fn() -> Duration>,
+}
+
+/// A handle for telling a [`Recorder`] the terminal was resized, the new
+/// size is recorded as an asciicast `"r"` event before the next frame.
+#[derive(Debug, Clone, Default)]
+pub struct Resizes(Arc<Mutex<Option<(u16, u16)>>>);
+
+impl Resizes {
+    pub fn resize(&self, width: u16, height: u16) {
+        *self.size() = Some((width, height));
+    }
+
+    fn size(&self) -> MutexGuard<'_, Option<(u16, u16)>> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<T: Write, C: Write> Recorder<T, C> {
+    /// Start a recording using the wall clock for event timestamps.
+    pub fn new(inner: T, cast: C, width: u16, height: u16) -> io::Result<Self> {
+        let start = Instant::now();
+        Self::with_clock(inner, cast, width, height, move || start.elapsed())
+    }
+
+    /// Start a recording where each event's timestamp is the value returned
+    /// by `clock`, this allows for generating a cast without waiting for the
+    /// time to actually elapse.
+    pub fn with_clock(
+        inner: T,
+        mut cast: C,
+        width: u16,
+        height: u16,
+        clock: impl Fn() -> Duration + 'static,
+    ) -> io::Result<Self> {
+        writeln!(
+            cast,
+            r#"{{"version": 2, "width": {width}, "height": {height}, "env": {{"TERM": "xterm-256color"}}}}"#
+        )?;
+        Ok(Self {
+            inner,
+            cast,
+            pending: Vec::new(),
+            resizes: Resizes::default(),
+            clock: Box::new(clock),
+        })
+    }
+
+    /// The handle the terminal's resize events should be sent to
+    pub fn resizes(&self) -> Resizes {
+        self.resizes.clone()
+    }
+
+    fn write_event(&mut self, output: &str) -> io::Result<()> {
+        let ts = (self.clock)().as_secs_f64();
+        writeln!(self.cast, r#"[{ts:.6}, "o", "{}"]"#, escape_json(output))
+    }
+
+    fn write_resize(&mut self, (width, height): (u16, u16)) -> io::Result<()> {
+        let ts = (self.clock)().as_secs_f64();
+        writeln!(self.cast, r#"[{ts:.6}, "r", "{width}x{height}"]"#)
+    }
+}
+
+impl<T: Write, C: Write> Write for Recorder<T, C> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = self.inner.write(buf)?;
+        self.pending.extend_from_slice(&buf[..len]);
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()?;
+        let resize = self.resizes.size().take();
+        if let Some(size) = resize {
+            self.write_resize(size)?;
+        }
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        let pending = core::mem::take(&mut self.pending);
+        self.write_event(&String::from_utf8_lossy(&pending))
+    }
+}
+
+impl<T: Write, C: Write> Drop for Recorder<T, C> {
+    /// Flush any remaining output and write a final empty event so the cast
+    /// holds the last frame until the recording ends.
+    fn drop(&mut self) {
+        let res = self
+            .flush()
+            .and_then(|_| self.write_event(""))
+            .and_then(|_| self.cast.flush());
+        if let Err(e) = res {
+            log::error!("failed to finish recording: {e}");
+        }
+    }
+}
+
+fn escape_json(s: &str) -> String {
+    let mut ret = String::with_capacity(s.len());
+    for ch in s.chars() {
+        match ch {
+            '"' => ret.push_str("\\\""),
+            '\\' => ret.push_str("\\\\"),
+            '\n' => ret.push_str("\\n"),
+            '\r' => ret.push_str("\\r"),
+            '\t' => ret.push_str("\\t"),
+            c if c.is_control() => ret.push_str(&format!("\\u{:04x}", c as u32)),
+            c => ret.push(c),
+        }
+    }
+    ret
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::Cell;
+
+    use super::*;
+
+    #[test]
+    fn record_frames() {
+        let mut output = Vec::new();
+        let mut cast = Vec::new();
+        {
+            let ticks = Cell::new(0);
+            let clock = move || {
+                ticks.set(ticks.get() + 1);
+                Duration::from_millis(500 * ticks.get())
+            };
+            let mut recorder = Recorder::with_clock(&mut output, &mut cast, 80, 24, clock).unwrap();
+            recorder.write_all(b"\x1b[1mhi\n\"there\"\\").unwrap();
+            recorder.flush().unwrap();
+            // nothing was written so no event is recorded
+            recorder.flush().unwrap();
+            recorder.write_all(b"bye").unwrap();
+        }
+        assert_eq!(output, b"\x1b[1mhi\n\"there\"\\bye");
+        let cast = String::from_utf8(cast).unwrap();
+        let lines: Vec<&str> = cast.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                r#"{"version": 2, "width": 80, "height": 24, "env": {"TERM": "xterm-256color"}}"#,
+                r#"[0.500000, "o", "\u001b[1mhi\n\"there\"\\"]"#,
+                r#"[1.000000, "o", "bye"]"#,
+                r#"[1.500000, "o", ""]"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn record_resizes() {
+        let mut cast = Vec::new();
+        {
+            let ticks = Cell::new(0);
+            let clock = move || {
+                ticks.set(ticks.get() + 1);
+                Duration::from_secs(ticks.get())
+            };
+            let mut recorder = Recorder::with_clock(io::sink(), &mut cast, 80, 24, clock).unwrap();
+            let resizes = recorder.resizes();
+            recorder.write_all(b"a").unwrap();
+            recorder.flush().unwrap();
+            // only the last size before a frame is recorded
+            resizes.resize(100, 30);
+            resizes.resize(120, 40);
+            recorder.write_all(b"b").unwrap();
+            recorder.flush().unwrap();
+            // a resize that didn't redraw anything is still recorded
+            resizes.resize(90, 20);
+            recorder.flush().unwrap();
+        }
+        let cast = String::from_utf8(cast).unwrap();
+        let lines: Vec<&str> = cast.lines().skip(1).collect();
+        assert_eq!(
+            lines,
+            [
+                r#"[1.000000, "o", "a"]"#,
+                r#"[2.000000, "r", "120x40"]"#,
+                r#"[3.000000, "o", "b"]"#,
+                r#"[4.000000, "r", "90x20"]"#,
+                r#"[5.000000, "o", ""]"#,
+            ]
+        );
+    }
+}
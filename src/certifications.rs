@@ -0,0 +1,203 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span, Text},
+    widgets::{List, ListItem, StatefulWidget, Widget},
+};
+
+use crate::{
+    data::{today, Certification},
+    date::Date,
+    list_state::ListStateWrapper as ListState,
+    Navigable, Page, DEFAULT_STYLE,
+};
+
+/// Certifications expiring within this many months are flagged as expiring
+/// soon
+const EXPIRING_SOON_MONTHS: i32 = 3;
+
+#[derive(Debug, Clone)]
+pub struct CertificationsView<'a> {
+    /// Sorted with the most recently issued first
+    certifications: Vec<&'a Certification<'a>>,
+    today: Date,
+    menu: ListState,
+}
+
+impl<'a> CertificationsView<'a> {
+    pub fn new(certifications: &'a [Certification<'a>]) -> Self {
+        let mut certifications: Vec<_> = certifications.iter().collect();
+        certifications.sort_by_key(|c| std::cmp::Reverse(c.issued));
+        Self {
+            menu: ListState::new(certifications.len().saturating_sub(1)),
+            certifications,
+            today: today(),
+        }
+    }
+
+    fn status(&self, cert: &Certification) -> Span<'static> {
+        let Some(expires) = cert.expires else {
+            return Span::raw("No expiration");
+        };
+        let remaining = expires.months() - self.today.months();
+        if remaining < 0 {
+            Span::raw(format!("Expired {expires}"))
+                .style(Style::new().fg(Color::Red).add_modifier(Modifier::BOLD))
+        } else if remaining <= EXPIRING_SOON_MONTHS {
+            Span::raw(format!("Expires soon, {expires}"))
+                .style(Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+        } else {
+            Span::raw(format!("Expires {expires}"))
+        }
+    }
+}
+
+impl<'a> Widget for CertificationsView<'a> {
+    fn render(mut self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        let list_items: Vec<ListItem> = self
+            .certifications
+            .iter()
+            .map(|c| {
+                let mut lines = vec![
+                    Line::from(c.name.add_modifier(Modifier::BOLD)),
+                    Line::from(format!("  {}", c.issuer)),
+                    Line::from(vec![
+                        Span::raw(format!("    Issued {} - ", c.issued)),
+                        self.status(c),
+                    ]),
+                ];
+                if let Some(id) = c.credential_id {
+                    lines.push(Line::from(format!("    Credential ID: {id}")));
+                }
+                if let Some(url) = c.url {
+                    lines.push(Line::from(format!("    {url}")));
+                }
+                ListItem::new(Text::from(lines))
+            })
+            .collect();
+        let list = List::new(list_items)
+            .highlight_style(Style::new().bg(Color::Green).fg(Color::Black))
+            .style(DEFAULT_STYLE);
+        StatefulWidget::render(list, area, buf, self.menu.as_mut());
+    }
+}
+
+impl<'a> Navigable for CertificationsView<'a> {
+    fn increment_selection(&mut self) {
+        self.menu.increment();
+    }
+
+    fn decrement_selection(&mut self) {
+        self.menu.decrement();
+    }
+
+    fn select_item(&mut self, idx: usize) {
+        self.menu.select_item(idx);
+    }
+
+    fn handle_enter(&mut self) {}
+
+    fn handle_left(&mut self) -> bool {
+        false
+    }
+
+    fn yank(&self) -> Option<String> {
+        let cert = self.certifications.get(self.menu.selected()?)?;
+        let mut ret = format!("{}, {} ({})", cert.name, cert.issuer, cert.issued);
+        if let Some(id) = cert.credential_id {
+            ret.push_str(&format!("\nCredential ID: {id}"));
+        }
+        if let Some(url) = cert.url {
+            ret.push('\n');
+            ret.push_str(url);
+        }
+        Some(ret)
+    }
+}
+
+impl<'a> Page for CertificationsView<'a> {
+    fn title(&self) -> &str {
+        "Certifications"
+    }
+
+    fn id(&self) -> &str {
+        "certifications"
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        Widget::render(self.clone(), area, buf);
+    }
+
+    fn reset(&mut self) {
+        self.today = today();
+        self.menu = ListState::new(self.certifications.len().saturating_sub(1));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const fn cert(
+        name: &'static str,
+        issued: Date,
+        expires: Option<Date>,
+    ) -> Certification<'static> {
+        Certification {
+            name,
+            issuer: "Issuer",
+            issued,
+            expires,
+            credential_id: None,
+            url: None,
+        }
+    }
+
+    #[test]
+    fn sort_most_recent_first() {
+        let certifications = [
+            cert("Old", Date::new(2019, 5), None),
+            cert("New", Date::new(2023, 1), None),
+            cert("Middle", Date::year(2021), None),
+        ];
+        let view = CertificationsView::new(&certifications);
+        let names: Vec<_> = view.certifications.iter().map(|c| c.name).collect();
+        assert_eq!(names, ["New", "Middle", "Old"]);
+        assert_eq!(view.yank(), Some("New, Issuer (January 2023)".to_string()));
+    }
+
+    #[test]
+    fn expiry_status() {
+        let mut view = CertificationsView::new(&[]);
+        view.today = Date::new(2024, 6);
+        let status = |expires: Option<Date>| {
+            let span = view.status(&cert("Cert", Date::year(2020), expires));
+            (span.content.into_owned(), span.style.fg)
+        };
+        assert_eq!(status(None), ("No expiration".to_string(), None));
+        // a certification is valid through the month it expires
+        assert_eq!(
+            status(Some(Date::new(2024, 5))),
+            ("Expired May 2024".to_string(), Some(Color::Red))
+        );
+        assert_eq!(
+            status(Some(Date::new(2024, 6))),
+            ("Expires soon, June 2024".to_string(), Some(Color::Yellow))
+        );
+        assert_eq!(
+            status(Some(Date::new(2024, 9))),
+            (
+                "Expires soon, September 2024".to_string(),
+                Some(Color::Yellow)
+            )
+        );
+        assert_eq!(
+            status(Some(Date::new(2024, 10))),
+            ("Expires October 2024".to_string(), None)
+        );
+    }
+}
@@ -0,0 +1,201 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Text},
+    widgets::{List, ListItem, StatefulWidget, Widget},
+};
+
+use crate::{
+    data::{phone_url, website_url, Contact},
+    list_state::ListStateWrapper as ListState,
+    Action, Navigable, Page, DEFAULT_STYLE,
+};
+
+#[derive(Debug, Clone)]
+pub struct ContactView<'a> {
+    contact: &'a Contact<'a>,
+    menu: ListState,
+    action: Option<Action>,
+}
+
+impl<'a> ContactView<'a> {
+    pub fn new(contact: &'a Contact<'a>) -> Self {
+        let len = Self::items(contact).len();
+        Self {
+            contact,
+            menu: ListState::new(len.saturating_sub(1)),
+            action: None,
+        }
+    }
+
+    /// Each contact method as a label, value and the url opened when it is
+    /// selected
+    fn items<'b>(contact: &Contact<'b>) -> Vec<(&'b str, String, Option<String>)> {
+        let mut ret: Vec<_> = [
+            (
+                "Email",
+                contact.email,
+                contact.email.map(|e| format!("mailto:{e}")),
+            ),
+            ("Website", contact.website, contact.website.map(website_url)),
+            ("Phone", contact.phone, contact.phone.map(phone_url)),
+            ("Location", contact.location, None),
+        ]
+        .into_iter()
+        .filter_map(|(label, value, url)| Some((label, value?.to_string(), url)))
+        .collect();
+        ret.extend(contact.profiles.iter().map(|p| {
+            let url = p.url();
+            let value = if let Some(url) = &url {
+                format!("{} ({url})", p.username)
+            } else {
+                p.username.to_string()
+            };
+            (p.network, value, url)
+        }));
+        ret
+    }
+}
+
+impl<'a> Widget for ContactView<'a> {
+    fn render(mut self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        let list_items: Vec<ListItem> = Self::items(self.contact)
+            .into_iter()
+            .map(|(label, value, _)| {
+                ListItem::new(Text::from(vec![
+                    Line::from(label.add_modifier(Modifier::BOLD)),
+                    Line::from(format!("  {value}")),
+                ]))
+            })
+            .collect();
+        let list = List::new(list_items)
+            .highlight_style(Style::new().bg(Color::Green).fg(Color::Black))
+            .style(DEFAULT_STYLE);
+        StatefulWidget::render(list, area, buf, self.menu.as_mut());
+    }
+}
+
+impl<'a> Navigable for ContactView<'a> {
+    fn increment_selection(&mut self) {
+        self.menu.increment();
+    }
+
+    fn decrement_selection(&mut self) {
+        self.menu.decrement();
+    }
+
+    fn select_item(&mut self, idx: usize) {
+        self.menu.select_item(idx);
+    }
+
+    fn handle_enter(&mut self) {
+        let Some(idx) = self.menu.selected() else {
+            return;
+        };
+        self.action = Self::items(self.contact)
+            .into_iter()
+            .nth(idx)
+            .and_then(|(_, _, url)| url)
+            .map(Action::Open);
+    }
+
+    fn handle_left(&mut self) -> bool {
+        false
+    }
+
+    fn take_action(&mut self) -> Option<Action> {
+        self.action.take()
+    }
+
+    fn yank(&self) -> Option<String> {
+        let idx = self.menu.selected()?;
+        let items = Self::items(self.contact);
+        // profiles are the last items, they are displayed with their url
+        // which is all that needs to be copied
+        let is_profile = idx >= items.len() - self.contact.profiles.len();
+        let (_, value, url) = items.into_iter().nth(idx)?;
+        if is_profile {
+            return url.or(Some(value));
+        }
+        Some(value)
+    }
+}
+
+impl<'a> Page for ContactView<'a> {
+    fn title(&self) -> &str {
+        "Contact"
+    }
+
+    fn id(&self) -> &str {
+        "contact"
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        Widget::render(self.clone(), area, buf);
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(self.contact);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ratatui::{backend::TestBackend, Terminal};
+
+    use super::*;
+    use crate::data::Profile;
+
+    static CONTACT: Contact = Contact {
+        email: Some("jane@example.com"),
+        website: Some("example.com"),
+        phone: Some("555 0100"),
+        location: Some("Minneapolis, MN"),
+        profiles: &[
+            Profile {
+                network: "GitHub",
+                username: "jane",
+                url: None,
+            },
+            Profile {
+                network: "Discord",
+                username: "jane",
+                url: None,
+            },
+        ],
+    };
+
+    #[test]
+    fn render_contact() {
+        let view = ContactView::new(&CONTACT);
+        let mut term = Terminal::new(TestBackend::new(40, 12)).unwrap();
+        term.draw(|f| f.render_widget(view.clone(), f.area()))
+            .unwrap();
+        insta::assert_snapshot!(term.backend());
+    }
+
+    #[test]
+    fn open_contact_links() {
+        let mut view = ContactView::new(&CONTACT);
+        let mut opened = Vec::new();
+        for _ in 0..6 {
+            view.handle_enter();
+            opened.push(view.take_action());
+            view.increment_selection();
+        }
+        let expected = [
+            Some("mailto:jane@example.com"),
+            Some("https://example.com"),
+            Some("tel:5550100"),
+            None,
+            Some("https://github.com/jane"),
+            None,
+        ];
+        let expected = expected.map(|url| url.map(|url| Action::Open(url.to_string())));
+        assert_eq!(opened, expected);
+    }
+}
@@ -1,46 +1,536 @@
+use crate::date::Date;
+
+/// The contents of a resume, the `DATABASE` static is generated from the
+/// `data` directory at build time but any number of these can be constructed
+/// in code, borrowing data loaded at runtime, and rendered with
+/// `App::with_database`
 #[derive(Debug, Clone)]
-pub struct Database {
-    pub name: &'static str,
-    pub tag_line: &'static str,
-    pub github: Option<&'static str>,
-    pub linkedin: Option<&'static str>,
-    pub jobs: &'static [Workplace],
-    pub open_source: &'static [Project],
-    pub education: &'static [School],
+pub struct Database<'a> {
+    pub name: &'a str,
+    pub tag_line: &'a str,
+    pub contact: Contact<'a>,
+    pub jobs: &'a [Workplace<'a>],
+    pub open_source: &'a [Project<'a>],
+    pub education: &'a [School<'a>],
+    pub publications: &'a [Publication<'a>],
+    pub talks: &'a [Talk<'a>],
+    /// Displayed with the most recently issued first
+    pub certifications: &'a [Certification<'a>],
+    pub sections: &'a [Section<'a>],
 }
 
 #[derive(Debug, Clone)]
-pub struct Workplace {
-    pub name: &'static str,
-    pub title: &'static str,
-    pub start: &'static str,
-    pub end: Option<&'static str>,
-    pub details: &'static [Detail],
+pub struct Contact<'a> {
+    pub email: Option<&'a str>,
+    pub website: Option<&'a str>,
+    pub phone: Option<&'a str>,
+    pub location: Option<&'a str>,
+    pub profiles: &'a [Profile<'a>],
+}
+
+impl<'a> Database<'a> {
+    /// The number of months worked across every job, months where more than
+    /// one job overlaps are only counted once
+    pub fn experience(&self, today: Date) -> i32 {
+        total_months(
+            self.jobs
+                .iter()
+                .map(|job| month_range(job.start, job.end, today)),
+        )
+    }
+
+    /// The months worked at jobs or roles with a detail tagged with each tag,
+    /// ordered from the most experience to the least
+    pub fn tag_experience(&self, today: Date) -> Vec<(&'a str, i32)> {
+        let mut tagged: Vec<(&'a str, (i32, i32))> = Vec::new();
+        for job in self.jobs {
+            let job_months = month_range(job.start, job.end, today);
+            let roles = job
+                .roles
+                .iter()
+                .map(|role| (month_range(role.start, role.end, today), role.details));
+            for (months, details) in Some((job_months, job.details)).into_iter().chain(roles) {
+                let tags = details.iter().flat_map(|d| d.tags.iter());
+                tagged.extend(tags.map(|tag| (*tag, months)));
+            }
+        }
+        tagged.sort_unstable();
+        tagged.dedup();
+        let mut ret: Vec<_> = tagged
+            .chunk_by(|(l, _), (r, _)| l == r)
+            .map(|chunk| {
+                let months = total_months(chunk.iter().map(|(_, months)| *months));
+                (chunk[0].0, months)
+            })
+            .collect();
+        ret.sort_by(|(l_tag, l), (r_tag, r)| r.cmp(l).then(l_tag.cmp(r_tag)));
+        ret
+    }
+}
+
+/// The first and last month of a date range, an ongoing range runs through
+/// `today`
+fn month_range(start: Date, end: Option<Date>, today: Date) -> (i32, i32) {
+    (start.months(), end.unwrap_or(today).months())
+}
+
+/// The number of months covered by the `(first, last)` month ranges
+fn total_months(ranges: impl Iterator<Item = (i32, i32)>) -> i32 {
+    let mut ranges: Vec<_> = ranges.collect();
+    ranges.sort_unstable();
+    let mut total = 0;
+    let mut counted = i32::MIN;
+    for (first, last) in ranges {
+        let first = first.max(counted + 1);
+        if last >= first {
+            total += last - first + 1;
+            counted = last;
+        }
+    }
+    total
+}
+
+impl<'a> Contact<'a> {
+    pub fn is_empty(&self) -> bool {
+        self.email.is_none()
+            && self.website.is_none()
+            && self.phone.is_none()
+            && self.location.is_none()
+            && self.profiles.is_empty()
+    }
+
+    /// The email, website, phone, location and profiles as the text to display
+    /// and the url to open, the location and a profile on an unknown network
+    /// without a `url` are displayed as plain text
+    pub fn links(&self) -> Vec<(String, Option<String>)> {
+        let email = self
+            .email
+            .map(|e| (e.to_string(), Some(format!("mailto:{e}"))));
+        let website = self.website.map(|w| (w.to_string(), Some(website_url(w))));
+        let phone = self.phone.map(|p| (p.to_string(), Some(phone_url(p))));
+        let location = self.location.map(|l| (l.to_string(), None));
+        let profiles = self.profiles.iter().map(|p| match p.url() {
+            Some(url) => (url.clone(), Some(url)),
+            None => (format!("{}: {}", p.network, p.username), None),
+        });
+        email
+            .into_iter()
+            .chain(website)
+            .chain(phone)
+            .chain(location)
+            .chain(profiles)
+            .collect()
+    }
 }
 
+/// Add a scheme to a website written without one, e.g. `example.com`
+pub(crate) fn website_url(website: &str) -> String {
+    if website.contains("://") {
+        website.to_string()
+    } else {
+        format!("https://{website}")
+    }
+}
+
+/// A `tel:` url for a phone number, spaces aren't allowed in the url
+pub(crate) fn phone_url(phone: &str) -> String {
+    format!("tel:{}", phone.replace(' ', ""))
+}
+
+/// An account on a social or code hosting network
 #[derive(Debug, Clone)]
-pub struct Detail {
-    pub headline: &'static str,
-    pub snippet: &'static str,
-    pub detail: &'static str,
+pub struct Profile<'a> {
+    pub network: &'a str,
+    pub username: &'a str,
+    /// Overrides the url built from the network and username
+    pub url: Option<&'a str>,
+}
+
+impl<'a> Profile<'a> {
+    /// The url for this profile, either the one provided or one built from the
+    /// username for well known networks
+    pub fn url(&self) -> Option<String> {
+        if let Some(url) = self.url {
+            return Some(url.to_string());
+        }
+        let user = self.username.trim_start_matches('@');
+        let url = match self.network.to_lowercase().as_str() {
+            "github" => format!("https://github.com/{user}"),
+            "gitlab" => format!("https://gitlab.com/{user}"),
+            "codeberg" => format!("https://codeberg.org/{user}"),
+            "sourcehut" | "sr.ht" => format!("https://sr.ht/~{user}"),
+            "linkedin" => format!("https://www.linkedin.com/in/{user}"),
+            "twitter" | "x" => format!("https://x.com/{user}"),
+            "bluesky" => format!("https://bsky.app/profile/{user}"),
+            "mastodon" => {
+                // mastodon usernames include the instance, `user@instance`
+                let (user, instance) = user.split_once('@')?;
+                format!("https://{instance}/@{user}")
+            }
+            "crates.io" | "crates" => format!("https://crates.io/users/{user}"),
+            "youtube" => format!("https://www.youtube.com/@{user}"),
+            _ => return None,
+        };
+        Some(url)
+    }
 }
 
 #[derive(Debug, Clone)]
-pub struct Project {
-    pub name: &'static str,
-    pub short_desc: &'static str,
-    pub long_desc: &'static str,
-    pub sub_projects: &'static [Project],
+pub struct Workplace<'a> {
+    pub name: &'a str,
+    /// The most recent role's title when the job has roles
+    pub title: &'a str,
+    pub start: Date,
+    /// `None` for a current job
+    pub end: Option<Date>,
+    /// Details that aren't tied to one of the `roles`
+    pub details: &'a [Detail<'a>],
+    /// The positions held at this employer, with the most recent first
+    pub roles: &'a [Role<'a>],
+}
+
+impl<'a> Workplace<'a> {
+    /// The number of months worked including the first and last month
+    pub fn tenure(&self, today: Date) -> i32 {
+        tenure(self.start, self.end, today)
+    }
+
+    /// The details that aren't tied to a role followed by each role's details
+    pub fn all_details(&self) -> impl Iterator<Item = &'a Detail<'a>> {
+        let roles = self.roles.iter().flat_map(|role| role.details.iter());
+        self.details.iter().chain(roles)
+    }
+}
+
+/// A title held for part of the time at a [`Workplace`], e.g. after a
+/// promotion
+#[derive(Debug, Clone)]
+pub struct Role<'a> {
+    pub title: &'a str,
+    pub start: Date,
+    /// `None` for the current role
+    pub end: Option<Date>,
+    pub details: &'a [Detail<'a>],
+}
+
+impl<'a> Role<'a> {
+    /// The number of months in this role including the first and last month
+    pub fn tenure(&self, today: Date) -> i32 {
+        tenure(self.start, self.end, today)
+    }
+}
+
+fn tenure(start: Date, end: Option<Date>, today: Date) -> i32 {
+    let (first, last) = month_range(start, end, today);
+    (last - first + 1).max(0)
 }
 
 #[derive(Debug, Clone)]
-pub struct School {
-    pub name: &'static str,
-    pub graduated: Option<&'static str>,
-    pub desc: &'static str,
+pub struct Detail<'a> {
+    pub id: u32,
+    pub headline: &'a str,
+    pub snippet: &'a str,
+    pub detail: &'a str,
+    /// Technologies or skills used, experience is totaled for each tag on the
+    /// Home page
+    pub tags: &'a [&'a str],
+}
+
+#[derive(Debug, Clone)]
+pub struct Project<'a> {
+    pub name: &'a str,
+    pub short_desc: &'a str,
+    pub long_desc: &'a str,
+    /// Projects with a start date are included in the Timeline
+    pub start: Option<Date>,
+    /// `None` for an active project
+    pub end: Option<Date>,
+    pub sub_projects: &'a [Project<'a>],
+}
+
+#[derive(Debug, Clone)]
+pub struct School<'a> {
+    pub name: &'a str,
+    pub start: Option<Date>,
+    pub graduated: Option<Date>,
+    pub desc: &'a str,
+}
+
+#[derive(Debug, Clone)]
+pub struct Publication<'a> {
+    pub title: &'a str,
+    pub venue: &'a str,
+    pub date: Date,
+    pub co_authors: &'a [&'a str],
+    pub url: Option<&'a str>,
+    /// The abstract, Commonmark markdown can be used to style this content
+    pub summary: &'a str,
+}
+
+#[derive(Debug, Clone)]
+pub struct Talk<'a> {
+    pub title: &'a str,
+    /// The conference or meetup this talk was given at
+    pub venue: &'a str,
+    pub date: Date,
+    pub co_authors: &'a [&'a str],
+    pub url: Option<&'a str>,
+    /// The abstract, Commonmark markdown can be used to style this content
+    pub summary: &'a str,
+}
+
+#[derive(Debug, Clone)]
+pub struct Certification<'a> {
+    pub name: &'a str,
+    pub issuer: &'a str,
+    pub issued: Date,
+    pub expires: Option<Date>,
+    pub credential_id: Option<&'a str>,
+    pub url: Option<&'a str>,
+}
+
+/// An additional page in the main menu defined in `sections.toml`
+#[derive(Debug, Clone)]
+pub struct Section<'a> {
+    pub title: &'a str,
+    pub entries: &'a [Entry<'a>],
+}
+
+#[derive(Debug, Clone)]
+pub struct Entry<'a> {
+    pub headline: &'a str,
+    pub snippet: &'a str,
+    pub body: &'a str,
+    pub start: Option<Date>,
+    /// Displayed as "Current" when there is a `start` but no `end`
+    pub end: Option<Date>,
+    pub children: &'a [Entry<'a>],
+}
+
+/// Character art generated at build time from an image file in the data
+/// directory, only generated with the `image-art` feature
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Image {
+    /// The path of the file relative to the data directory
+    pub path: &'static str,
+    /// The width in pixels, each pixel is drawn as half of a character cell
+    pub width: usize,
+    /// The pixels row by row, transparent pixels are `None`
+    pub pixels: &'static [Option<[u8; 3]>],
+}
+
+impl Image {
+    /// The character art for an image referenced as `path` in markdown
+    pub(crate) fn find(path: &str) -> Option<&'static Image> {
+        let path = path.trim_start_matches("./");
+        source::IMAGES.iter().find(|image| image.path == path)
+    }
+
+    pub(crate) fn height(&self) -> usize {
+        self.pixels.len() / self.width.max(1)
+    }
+}
+
+/// The current month, when compiled for wasm the system clock isn't available
+/// so the browser's clock is used instead
+#[cfg(target_arch = "wasm32")]
+pub fn today() -> Date {
+    let now = js_sys::Date::new_0();
+    Date::new(now.get_full_year() as u16, now.get_month() as u8 + 1)
+}
+
+/// The current month, falling back to the month this crate was built if the
+/// system clock is set before the unix epoch
+#[cfg(not(target_arch = "wasm32"))]
+pub fn today() -> Date {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(source::BUILD_DATE, |now| {
+            Date::from_unix_secs(now.as_secs())
+        })
 }
 
 pub mod source {
     use super::*;
     include!(concat!(env!("OUT_DIR"), "/source_data.rs"));
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const fn job(start: Date, end: Option<Date>) -> Workplace<'static> {
+        Workplace {
+            name: "",
+            title: "",
+            start,
+            end,
+            details: &[],
+            roles: &[],
+        }
+    }
+
+    fn database<'a>(jobs: &'a [Workplace<'a>]) -> Database<'a> {
+        Database {
+            name: "",
+            tag_line: "",
+            contact: Contact {
+                email: None,
+                website: None,
+                phone: None,
+                location: None,
+                profiles: &[],
+            },
+            jobs,
+            open_source: &[],
+            education: &[],
+            publications: &[],
+            talks: &[],
+            certifications: &[],
+            sections: &[],
+        }
+    }
+
+    const fn profile(network: &'static str, username: &'static str) -> Profile<'static> {
+        Profile {
+            network,
+            username,
+            url: None,
+        }
+    }
+
+    #[test]
+    fn profile_urls() {
+        let urls = [
+            ("GitHub", "jane", "https://github.com/jane"),
+            ("gitlab", "jane", "https://gitlab.com/jane"),
+            ("Codeberg", "jane", "https://codeberg.org/jane"),
+            ("SourceHut", "jane", "https://sr.ht/~jane"),
+            ("sr.ht", "jane", "https://sr.ht/~jane"),
+            ("LinkedIn", "jane", "https://www.linkedin.com/in/jane"),
+            ("Twitter", "@jane", "https://x.com/jane"),
+            ("X", "jane", "https://x.com/jane"),
+            (
+                "Bluesky",
+                "jane.bsky.social",
+                "https://bsky.app/profile/jane.bsky.social",
+            ),
+            (
+                "Mastodon",
+                "@jane@hachyderm.io",
+                "https://hachyderm.io/@jane",
+            ),
+            ("crates.io", "jane", "https://crates.io/users/jane"),
+            ("crates", "jane", "https://crates.io/users/jane"),
+            ("YouTube", "jane", "https://www.youtube.com/@jane"),
+        ];
+        for (network, username, url) in urls {
+            assert_eq!(
+                profile(network, username).url().as_deref(),
+                Some(url),
+                "{network}"
+            );
+        }
+        // a mastodon username without an instance can't be linked
+        assert_eq!(profile("Mastodon", "jane").url(), None);
+    }
+
+    #[test]
+    fn profile_url_fallback() {
+        assert_eq!(profile("Discord", "jane").url(), None);
+        let custom = Profile {
+            url: Some("https://example.com/jane"),
+            ..profile("Discord", "jane")
+        };
+        assert_eq!(custom.url().as_deref(), Some("https://example.com/jane"));
+        // a provided url wins over the one built for a known network
+        let github = Profile {
+            url: Some("https://github.com/jane-doe"),
+            ..profile("GitHub", "jane")
+        };
+        assert_eq!(github.url().as_deref(), Some("https://github.com/jane-doe"));
+    }
+
+    #[test]
+    fn contact_links_keep_plain_profiles() {
+        let contact = Contact {
+            email: Some("jane@example.com"),
+            website: Some("example.com"),
+            phone: Some("555 0100"),
+            location: Some("Minneapolis, MN"),
+            profiles: &[profile("GitHub", "jane"), profile("Discord", "jane")],
+        };
+        assert_eq!(
+            contact.links(),
+            [
+                (
+                    "jane@example.com".to_string(),
+                    Some("mailto:jane@example.com".to_string())
+                ),
+                (
+                    "example.com".to_string(),
+                    Some("https://example.com".to_string())
+                ),
+                ("555 0100".to_string(), Some("tel:5550100".to_string())),
+                ("Minneapolis, MN".to_string(), None),
+                (
+                    "https://github.com/jane".to_string(),
+                    Some("https://github.com/jane".to_string())
+                ),
+                ("Discord: jane".to_string(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn tenure_includes_first_and_last_month() {
+        let today = Date::new(2021, 2);
+        assert_eq!(
+            job(Date::new(2020, 3), Some(Date::new(2020, 3))).tenure(today),
+            1
+        );
+        assert_eq!(
+            job(Date::new(2020, 1), Some(Date::new(2020, 3))).tenure(today),
+            3
+        );
+        assert_eq!(
+            job(Date::new(2019, 2), Some(Date::new(2020, 3))).tenure(today),
+            14
+        );
+        // a current job runs through this month
+        assert_eq!(job(Date::new(2020, 3), None).tenure(today), 12);
+        // a start without a month is January
+        assert_eq!(
+            job(Date::year(2020), Some(Date::new(2020, 3))).tenure(today),
+            3
+        );
+        assert_eq!(
+            job(Date::new(2020, 3), Some(Date::new(2020, 1))).tenure(today),
+            0
+        );
+    }
+
+    #[test]
+    fn back_to_back_jobs_share_a_month() {
+        static JOBS: &[Workplace] = &[
+            job(Date::new(2020, 3), None),
+            job(Date::new(2019, 2), Some(Date::new(2020, 3))),
+        ];
+        let today = Date::new(2021, 2);
+        let db = database(JOBS);
+        let tenures: i32 = JOBS.iter().map(|job| job.tenure(today)).sum();
+        assert_eq!(tenures, 26);
+        // March 2020 is displayed in the tenure of both jobs but only adds
+        // one month to the total
+        assert_eq!(db.experience(today), 25);
+    }
+
+    #[test]
+    fn overlapping_months_counted_once() {
+        assert_eq!(total_months([].into_iter()), 0);
+        assert_eq!(total_months([(0, 11)].into_iter()), 12);
+        assert_eq!(total_months([(12, 23), (0, 11)].into_iter()), 24);
+        assert_eq!(total_months([(0, 11), (6, 17)].into_iter()), 18);
+        assert_eq!(total_months([(0, 23), (6, 11), (30, 30)].into_iter()), 25);
+    }
+}
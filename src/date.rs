@@ -0,0 +1,178 @@
+//! A month and year date shared between the build script and the library so
+//! dates can be validated when the resume data is parsed.
+
+use std::{cmp::Ordering, fmt};
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// A calendar month, the month is optional to allow for dates like a
+/// graduation year. When comparing dates a missing month is treated as
+/// January, sorting just before an explicit January of the same year.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Date {
+    pub year: u16,
+    /// 1 through 12
+    pub month: Option<u8>,
+}
+
+impl Date {
+    /// Panics if `month` isn't 1 through 12
+    pub const fn new(year: u16, month: u8) -> Self {
+        assert!(month >= 1 && month <= 12, "month must be 1 through 12");
+        Self {
+            year,
+            month: Some(month),
+        }
+    }
+
+    pub const fn year(year: u16) -> Self {
+        Self { year, month: None }
+    }
+
+    /// The number of months since year 0, used for comparing dates and
+    /// computing durations
+    pub fn months(&self) -> i32 {
+        self.year as i32 * 12 + self.month.unwrap_or(1) as i32 - 1
+    }
+
+    /// The month containing the provided unix timestamp
+    pub fn from_unix_secs(secs: u64) -> Self {
+        // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let z = (secs / 86_400) as i64 + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z - era * 146_097;
+        let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + (month <= 2) as i64;
+        Self::new(year as u16, month as u8)
+    }
+
+    /// Parse a date in one of the following formats
+    ///
+    /// - `March 2020` or `Mar 2020`
+    /// - `2020-03` or `2020-03-14` (the day is ignored)
+    /// - `03/2020`
+    /// - `2020`
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let s = s.trim();
+        let invalid = || format!("invalid date `{s}`, expected a date like `March 2020`");
+        if let Some((month, year)) = s.split_once(char::is_whitespace) {
+            let month = month.trim_end_matches(['.', ',']).to_lowercase();
+            let idx = MONTHS
+                .iter()
+                .position(|m| {
+                    let m = m.to_lowercase();
+                    m == month || (month.len() >= 3 && m.starts_with(&month))
+                })
+                .ok_or_else(invalid)?;
+            let year = year.trim().parse().map_err(|_| invalid())?;
+            return Ok(Self::new(year, idx as u8 + 1));
+        }
+        let (year, month) = if let Some((month, year)) = s.split_once('/') {
+            (year, Some(month))
+        } else if let Some((year, rest)) = s.split_once('-') {
+            (year, rest.split('-').next())
+        } else {
+            (s, None)
+        };
+        let year = year.parse().map_err(|_| invalid())?;
+        let Some(month) = month else {
+            return Ok(Self::year(year));
+        };
+        let month: u8 = month.parse().map_err(|_| invalid())?;
+        if !(1..=12).contains(&month) {
+            return Err(invalid());
+        }
+        Ok(Self::new(year, month))
+    }
+}
+
+impl Ord for Date {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let key = |date: &Self| (date.year, date.month.unwrap_or(1), date.month.is_some());
+        key(self).cmp(&key(other))
+    }
+}
+
+impl PartialOrd for Date {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let month = self.month.and_then(|m| m.checked_sub(1));
+        if let Some(month) = month.and_then(|m| MONTHS.get(m as usize)) {
+            write!(f, "{month} ")?;
+        }
+        write!(f, "{}", self.year)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_dates() {
+        assert_eq!(Date::parse("March 2020"), Ok(Date::new(2020, 3)));
+        assert_eq!(Date::parse("sept. 2021"), Ok(Date::new(2021, 9)));
+        assert_eq!(Date::parse("2019-02"), Ok(Date::new(2019, 2)));
+        assert_eq!(Date::parse("2019-02-28"), Ok(Date::new(2019, 2)));
+        assert_eq!(Date::parse("12/2018"), Ok(Date::new(2018, 12)));
+        assert_eq!(Date::parse("2008"), Ok(Date::year(2008)));
+        assert!(Date::parse("Smarch 2020").is_err());
+        assert!(Date::parse("2020-13").is_err());
+        assert_eq!(Date::new(2020, 3).to_string(), "March 2020");
+        assert_eq!(Date::year(2008).to_string(), "2008");
+        assert_eq!(Date::from_unix_secs(1_709_251_200), Date::new(2024, 3));
+    }
+
+    #[test]
+    fn missing_month_sorts_as_january() {
+        let mut dates = vec![
+            Date::new(2020, 2),
+            Date::new(2020, 1),
+            Date::year(2020),
+            Date::new(2019, 12),
+        ];
+        dates.sort();
+        assert_eq!(
+            dates,
+            vec![
+                Date::new(2019, 12),
+                Date::year(2020),
+                Date::new(2020, 1),
+                Date::new(2020, 2),
+            ]
+        );
+        // a month outside of 1 through 12 can't be displayed
+        let invalid = Date {
+            year: 2020,
+            month: Some(0),
+        };
+        assert_eq!(invalid.to_string(), "2020");
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_rejects_invalid_months() {
+        Date::new(2020, 13);
+    }
+}
@@ -1,65 +1,316 @@
-use std::sync::{atomic::AtomicUsize, Arc};
+use std::{
+    ops::Range,
+    sync::{atomic::AtomicUsize, Arc},
+};
 
 use ratatui::{
     buffer::Buffer,
-    layout::{Constraint, Layout, Rect},
-    style::Modifier,
-    text::Text,
+    layout::{Constraint, Layout, Position, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span, Text},
     widgets::{
-        Block, Borders, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, StatefulWidget,
-        Widget, Wrap,
+        Block, Borders, List, ListItem, ListState, Paragraph, Scrollbar, ScrollbarOrientation,
+        ScrollbarState, StatefulWidget, Widget,
     },
 };
 
-use crate::{data::Detail, markdown::convert_md, Navigable, DEFAULT_STYLE};
+use crate::{
+    data::Image,
+    front_matter::Field,
+    graphics::Placement,
+    markdown::{convert_toggled, Heading, Link},
+    Action, Context, Navigable, DEFAULT_STYLE,
+};
+
+/// The narrowest view the outline of headings is displayed in
+const MIN_OUTLINE_VIEW_WIDTH: u16 = 60;
 
 #[derive(Debug, Clone)]
 pub struct DetailView<'a> {
-    title: &'static str,
+    title: &'a str,
+    /// The markdown the content was converted from
+    source: String,
     content: Text<'a>,
+    /// The width the content was converted for, tables are fit to the width
+    /// so the content is converted again when it changes
+    content_width: Option<usize>,
+    links: Vec<Link>,
+    headings: Vec<Heading>,
+    /// Where the art for each image was drawn in the content
+    images: Vec<Placement>,
+    /// The front matter, displayed under the title
+    metadata: Vec<Field>,
+    /// The `<details>` sections expanded or collapsed by the user
+    toggled: Vec<usize>,
+    /// Where links and images are registered when displayed with escape
+    /// sequences
+    context: Context,
+    /// The index of the focused link
+    link: Option<usize>,
+    action: Option<Action>,
     scroll: usize,
     scroll_max: Arc<AtomicUsize>,
+    /// The size of the content area from the last render, used to scroll
+    /// the focused link into view
+    view_width: Arc<AtomicUsize>,
+    view_height: Arc<AtomicUsize>,
 }
 
 impl<'a> DetailView<'a> {
-    pub fn new(title: &'static str, content: &'static str) -> Self {
+    pub fn new(title: &'a str, source: &str, context: Context) -> Self {
+        let doc = convert_toggled(
+            source,
+            context.hyperlinks.enabled(),
+            None,
+            &[],
+            &context.theme,
+        );
         Self {
             title,
-            content: convert_md(content),
+            source: source.to_string(),
+            content: doc.text,
+            content_width: None,
+            links: doc.links,
+            headings: doc.headings,
+            images: doc.images,
+            metadata: doc.metadata,
+            toggled: Vec::new(),
+            context,
+            link: None,
+            action: None,
             scroll: 0,
             scroll_max: Arc::new(AtomicUsize::new(100)),
+            view_width: Arc::new(AtomicUsize::new(0)),
+            view_height: Arc::new(AtomicUsize::new(0)),
         }
     }
-}
 
-impl<'a> From<&'a Detail> for DetailView<'a> {
-    fn from(detail: &'a Detail) -> Self {
-        Self::new(detail.headline, detail.detail)
+    pub fn link_focused(&self) -> bool {
+        self.link.is_some()
+    }
+
+    /// Convert the source again if `width` differs from the width the
+    /// content was converted for
+    fn fit_to(&mut self, width: usize) {
+        if self.content_width == Some(width) {
+            return;
+        }
+        let doc = convert_toggled(
+            &self.source,
+            self.context.hyperlinks.enabled(),
+            Some(width),
+            &self.toggled,
+            &self.context.theme,
+        );
+        self.content = doc.text;
+        self.links = doc.links;
+        self.headings = doc.headings;
+        self.images = doc.images;
+        self.metadata = doc.metadata;
+        self.content_width = Some(width);
+    }
+
+    /// The outline is only displayed when there are multiple headings and
+    /// enough room for it next to the content
+    fn outline_width(&self, width: u16) -> Option<u16> {
+        if self.headings.len() < 2 || width < MIN_OUTLINE_VIEW_WIDTH {
+            return None;
+        }
+        let longest = self
+            .headings
+            .iter()
+            .map(|h| Line::raw(&h.text).width() + self.heading_indent(h))
+            .max()
+            .unwrap_or_default();
+        // a border on the left and a space on either side
+        Some((longest as u16 + 3).min(width / 4))
+    }
+
+    fn heading_indent(&self, heading: &Heading) -> usize {
+        let top = self.headings.iter().map(|h| h.level).min().unwrap_or(1);
+        (heading.level - top) as usize * 2
+    }
+
+    /// The row each heading starts on
+    fn heading_rows(&self) -> Vec<usize> {
+        self.headings.iter().map(|h| h.line).collect()
+    }
+
+    /// The index of the heading of the section at the top of the view
+    fn current_section(&self, rows: &[usize]) -> Option<usize> {
+        rows.iter().rposition(|row| *row <= self.scroll)
+    }
+
+    fn render_outline(&self, area: Rect, rows: &[usize], buf: &mut Buffer) {
+        let block = Block::new()
+            .borders(Borders::LEFT)
+            .border_style(DEFAULT_STYLE)
+            .style(DEFAULT_STYLE);
+        let items: Vec<_> = self
+            .headings
+            .iter()
+            .map(|h| ListItem::new(format!(" {}{}", " ".repeat(self.heading_indent(h)), h.text)))
+            .collect();
+        let mut state = ListState::default().with_selected(self.current_section(rows));
+        StatefulWidget::render(
+            List::new(items)
+                .block(block)
+                .highlight_style(Style::new().bg(Color::Green).fg(Color::Black)),
+            area,
+            buf,
+            &mut state,
+        );
+    }
+
+    /// A line for each front matter field followed by a blank line
+    fn metadata_lines(&self) -> Vec<Line<'static>> {
+        if self.metadata.is_empty() {
+            return Vec::new();
+        }
+        self.metadata
+            .iter()
+            .map(|field| {
+                Line::from(vec![
+                    Span::styled(
+                        format!("{}: ", field.key),
+                        DEFAULT_STYLE.add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(field.values.join(", "), DEFAULT_STYLE),
+                ])
+            })
+            .chain(Some(Line::default()))
+            .collect()
+    }
+
+    /// Register the cells each visible link is drawn in, every line of the
+    /// content is one row so a link's cells follow from the spans it was
+    /// converted to
+    fn register_hyperlinks(&self, area: Rect, offset: usize) {
+        // footnote markers and summaries act within the detail so they aren't
+        // hyperlinks
+        for link in self.links.iter().filter(|l| !l.is_internal()) {
+            let positions = link.spans.iter().flat_map(|(line, range)| {
+                let row = line
+                    .checked_sub(offset)
+                    .filter(|row| *row < area.height as usize);
+                let spans = &self.content.lines[*line].spans;
+                let start: usize = spans[..range.start].iter().map(Span::width).sum();
+                let width: usize = spans[range.clone()].iter().map(Span::width).sum();
+                row.into_iter().flat_map(move |row| {
+                    (start..start + width)
+                        .take_while(move |col| *col < area.width as usize)
+                        .map(move |col| Position::new(area.x + col as u16, area.y + row as u16))
+                })
+            });
+            self.context.hyperlinks.register(positions, &link.url);
+        }
+    }
+
+    /// The area on screen of each image whose art is entirely visible, the
+    /// art is left as is when the image is partly scrolled out of view
+    fn visible_images(&self, area: Rect, offset: usize) -> Vec<(Rect, &'static Image)> {
+        self.images
+            .iter()
+            .filter(|image| {
+                image.line >= offset
+                    && image.line + image.rows <= offset + area.height as usize
+                    && image.column + image.columns <= area.width as usize
+            })
+            .map(|image| {
+                let rect = Rect::new(
+                    area.x + image.column as u16,
+                    area.y + (image.line - offset) as u16,
+                    image.columns as u16,
+                    image.rows as u16,
+                );
+                (rect, image.image)
+            })
+            .collect()
+    }
+
+    /// Scroll just enough to display the lines the focused link is on
+    fn scroll_to_link(&mut self) {
+        let width = self.view_width.load(std::sync::atomic::Ordering::Relaxed);
+        let height = self.view_height.load(std::sync::atomic::Ordering::Relaxed);
+        if width == 0 {
+            return;
+        }
+        self.fit_to(width);
+        let Some(link) = self.link.and_then(|idx| self.links.get(idx)) else {
+            return;
+        };
+        let Range {
+            start: top,
+            end: bottom,
+        } = link.lines();
+        if top < self.scroll {
+            self.scroll = top;
+        } else if bottom > self.scroll + height {
+            self.scroll = bottom.saturating_sub(height);
+        }
     }
 }
 
 impl<'a> Widget for DetailView<'a> {
-    fn render(self, area: Rect, buf: &mut Buffer) {
-        let [title, detail] =
-            Layout::vertical([Constraint::Length(2), Constraint::Fill(1)]).areas(area);
+    fn render(mut self, area: Rect, buf: &mut Buffer) {
+        let metadata = self.metadata_lines();
+        let [title, metadata_area, detail] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Length(metadata.len() as u16),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
         let block = Block::new()
             .borders(Borders::BOTTOM)
             .border_style(DEFAULT_STYLE.add_modifier(Modifier::BOLD));
 
+        let outline_width = self.outline_width(detail.width).unwrap_or_default();
+        let [detail, outline] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Length(outline_width)])
+                .areas(detail);
         let [content, scroll_bar] =
             Layout::horizontal([Constraint::Fill(1), Constraint::Length(1)]).areas(detail);
+        self.fit_to(content.width as _);
+        if outline_width > 0 {
+            let rows = self.heading_rows();
+            self.render_outline(outline, &rows, buf);
+        }
         let text = Text::raw(self.title).style(DEFAULT_STYLE.add_modifier(Modifier::BOLD));
         let block_area = block.inner(title);
         block.render(title, buf);
         text.render(block_area, buf);
-        let height =
-            calc_lines(&self.content, content.width as _).saturating_sub(content.height as _);
+        Paragraph::new(metadata).render(metadata_area, buf);
+        let height = self.content.lines.len().saturating_sub(content.height as _);
         self.scroll_max
             .store(height, std::sync::atomic::Ordering::Relaxed);
-        let para: Paragraph<'_> = Paragraph::new(self.content)
-            .wrap(Wrap { trim: false })
-            .scroll(((self.scroll as u16).min(height as _), 0));
+        self.view_width
+            .store(content.width as _, std::sync::atomic::Ordering::Relaxed);
+        self.view_height
+            .store(content.height as _, std::sync::atomic::Ordering::Relaxed);
+        if let Some(link) = self.link.and_then(|idx| self.links.get(idx)) {
+            for (line, spans) in &link.spans {
+                let Some(line) = self.content.lines.get_mut(*line) else {
+                    continue;
+                };
+                for span in line.spans.iter_mut().take(spans.end).skip(spans.start) {
+                    span.style = span.style.add_modifier(Modifier::REVERSED);
+                }
+            }
+        }
+        let offset = self.scroll.min(height);
+        if self.context.hyperlinks.enabled() {
+            self.register_hyperlinks(content, offset);
+        }
+        let images = if self.context.images.protocol().is_some() {
+            self.visible_images(content, offset)
+        } else {
+            Vec::new()
+        };
+        let para: Paragraph<'_> = Paragraph::new(self.content).scroll((offset as u16, 0));
         para.render(content, buf);
+        for (area, image) in images {
+            self.context.images.register(buf, area, image);
+        }
         let scroll = Scrollbar::new(ScrollbarOrientation::VerticalRight);
         let mut scroll_state = ScrollbarState::new(height)
             .viewport_content_length(content.height as _)
@@ -81,25 +332,146 @@ impl<'a> Navigable for DetailView<'a> {
         self.scroll = self.scroll.saturating_sub(1);
     }
 
-    fn handle_enter(&mut self) {}
+    fn handle_enter(&mut self) {
+        let Some(link) = self.link.and_then(|idx| self.links.get(idx)) else {
+            return;
+        };
+        if let Some(idx) = link.toggle {
+            // the links before the summary are unchanged so it keeps focus
+            match self.toggled.iter().position(|t| *t == idx) {
+                Some(pos) => {
+                    self.toggled.remove(pos);
+                }
+                None => self.toggled.push(idx),
+            }
+            self.content_width = None;
+            self.scroll_to_link();
+        } else if link.jump.is_some() {
+            self.link = link.jump;
+            self.scroll_to_link();
+        } else {
+            self.action = Some(Action::Open(link.url.clone()));
+        }
+    }
 
     fn handle_left(&mut self) -> bool {
         false
     }
-}
 
-fn calc_lines(text: &Text, view_width: usize) -> usize {
-    let mut ret = 0;
-    for line in &text.lines {
-        ret += 1;
-        let mut current_width = 0;
-        for span in &line.spans {
-            current_width += span.width();
-            if current_width >= view_width {
-                ret += 1;
-                current_width -= view_width;
-            }
+    fn focus_next_link(&mut self) {
+        self.link = match self.link {
+            None if !self.links.is_empty() => Some(0),
+            Some(idx) if idx + 1 < self.links.len() => Some(idx + 1),
+            _ => None,
+        };
+        self.scroll_to_link();
+    }
+
+    fn jump_to_heading(&mut self, forward: bool) {
+        let width = self.view_width.load(std::sync::atomic::Ordering::Relaxed);
+        if width == 0 {
+            return;
+        }
+        self.fit_to(width);
+        let rows = self.heading_rows();
+        let target = if forward {
+            rows.iter().find(|row| **row > self.scroll)
+        } else {
+            rows.iter().rev().find(|row| **row < self.scroll)
+        };
+        if let Some(row) = target {
+            let max = self.scroll_max.load(std::sync::atomic::Ordering::Relaxed);
+            self.scroll = (*row).min(max);
+        }
+    }
+
+    fn fit_to_view(&mut self) {
+        let width = self.view_width.load(std::sync::atomic::Ordering::Relaxed);
+        if width > 0 {
+            self.fit_to(width);
+        }
+    }
+
+    fn take_action(&mut self) -> Option<Action> {
+        self.action.take()
+    }
+
+    fn yank(&self) -> Option<String> {
+        match self.link.and_then(|idx| self.links.get(idx)) {
+            Some(link) if !link.is_internal() => Some(link.url.clone()),
+            _ => Some(self.source.clone()),
         }
     }
-    ret
+}
+
+#[cfg(test)]
+mod test {
+    use ratatui::{backend::TestBackend, Terminal};
+
+    use super::*;
+    use crate::hyperlink;
+
+    const SECTIONS: &str = "# Overview\n\nOne\n\n## Details\n\nTwo\n\nThree\n\n### Deeper\n\nFour\n\n## Results\n\nFive\n\nSix\n\nSeven";
+
+    /// The text of the cells linked to `url` on each row
+    fn linked(registry: &hyperlink::Registry, buf: &Buffer, url: &str) -> Vec<String> {
+        (buf.area.top()..buf.area.bottom())
+            .map(|y| {
+                (buf.area.left()..buf.area.right())
+                    .filter(|x| registry.url_at(*x, y).as_deref() == Some(url))
+                    .map(|x| buf[(x, y)].symbol().to_string())
+                    .collect::<String>()
+            })
+            .filter(|row| !row.is_empty())
+            .collect()
+    }
+
+    #[test]
+    fn register_hyperlinks() {
+        let registry = hyperlink::Registry::new(true);
+        let context = Context {
+            hyperlinks: registry.clone(),
+            ..Context::default()
+        };
+        let md = "x <u>under</u> and [link](https://x.example)\n\n> see [a link that wraps](https://a.example) here";
+        let area = Rect::new(0, 0, 21, 8);
+        let mut buf = Buffer::empty(area);
+        DetailView::new("Title", md, context).render(area, &mut buf);
+        assert_eq!(linked(&registry, &buf, "https://x.example"), vec!["link"]);
+        assert_eq!(
+            linked(&registry, &buf, "https://a.example"),
+            vec!["a link that", "wraps"]
+        );
+    }
+
+    #[test]
+    fn render_outline() {
+        let mut view = DetailView::new("Title", SECTIONS, Context::default());
+        let mut term = Terminal::new(TestBackend::new(MIN_OUTLINE_VIEW_WIDTH, 10)).unwrap();
+        term.draw(|f| f.render_widget(view.clone(), f.area()))
+            .unwrap();
+        // the section at the top of the view is selected in the outline
+        view.jump_to_heading(true);
+        term.draw(|f| f.render_widget(view.clone(), f.area()))
+            .unwrap();
+        insta::assert_snapshot!(term.backend());
+    }
+
+    #[test]
+    fn jump_between_headings() {
+        let mut view = DetailView::new("Title", SECTIONS, Context::default());
+        let area = Rect::new(0, 0, 40, 8);
+        view.clone().render(area, &mut Buffer::empty(area));
+        let rows = view.heading_rows();
+        assert_eq!(rows.len(), 4);
+        view.jump_to_heading(true);
+        assert_eq!(view.scroll, rows[1]);
+        view.jump_to_heading(true);
+        assert_eq!(view.scroll, rows[2]);
+        view.jump_to_heading(false);
+        assert_eq!(view.scroll, rows[1]);
+        view.jump_to_heading(false);
+        view.jump_to_heading(false);
+        assert_eq!(view.scroll, 0);
+    }
 }
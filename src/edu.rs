@@ -1,30 +1,49 @@
 use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
     symbols,
     text::{Line, Span, Text},
     widgets::{List, ListItem, Widget},
 };
 
-use crate::{data::source::DATABASE, Navigable};
+use crate::{data::School, work::date_range, Navigable, Page};
 
-#[derive(Debug, Clone, Default)]
-pub struct EduView;
+#[derive(Debug, Clone)]
+pub struct EduView<'a> {
+    schools: &'a [School<'a>],
+}
 
-impl Widget for EduView {
-    fn render(self, area: ratatui::prelude::Rect, buf: &mut ratatui::prelude::Buffer)
+impl<'a> EduView<'a> {
+    pub fn new(schools: &'a [School<'a>]) -> Self {
+        Self { schools }
+    }
+}
+
+impl<'a> Widget for EduView<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer)
     where
         Self: Sized,
     {
         List::new(
-            DATABASE
-                .education
+            self.schools
                 .iter()
                 .map(|e| {
                     let mut items = vec![
                         Line::from(e.name),
                         Line::from(vec![Span::from("  "), e.desc.into()]),
                     ];
-                    if let Some(grad) = e.graduated {
-                        items.push(Line::from(vec![Span::from("  Graduated: "), grad.into()]));
+                    // a school without a graduation date is still being
+                    // attended, like a current job
+                    match (e.start, e.graduated) {
+                        (Some(start), graduated) => items.push(Line::from(vec![
+                            Span::from("  "),
+                            date_range(start, graduated).into(),
+                        ])),
+                        (None, Some(grad)) => items.push(Line::from(vec![
+                            Span::from("  Graduated: "),
+                            grad.to_string().into(),
+                        ])),
+                        (None, None) => {}
                     }
                     items.push(Line::from(
                         symbols::line::HORIZONTAL.repeat(area.width as _),
@@ -37,7 +56,7 @@ impl Widget for EduView {
     }
 }
 
-impl Navigable for EduView {
+impl<'a> Navigable for EduView<'a> {
     fn increment_selection(&mut self) {}
 
     fn decrement_selection(&mut self) {}
@@ -48,3 +67,59 @@ impl Navigable for EduView {
         false
     }
 }
+
+impl<'a> Page for EduView<'a> {
+    fn title(&self) -> &str {
+        "Education"
+    }
+
+    fn id(&self) -> &str {
+        "edu"
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        Widget::render(self.clone(), area, buf);
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(self.schools);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ratatui::{backend::TestBackend, Terminal};
+
+    use super::*;
+    use crate::date::Date;
+
+    static SCHOOLS: &[School] = &[
+        School {
+            name: "State U",
+            start: Some(Date::new(2015, 9)),
+            graduated: Some(Date::new(2019, 5)),
+            desc: "BS Computer Science",
+        },
+        School {
+            name: "Tech",
+            start: Some(Date::new(2023, 1)),
+            graduated: None,
+            desc: "MS Computer Science",
+        },
+        School {
+            name: "Bootcamp",
+            start: None,
+            graduated: Some(Date::year(2014)),
+            desc: "Web Development",
+        },
+    ];
+
+    #[test]
+    fn render_school_dates() {
+        let view = EduView::new(SCHOOLS);
+        let mut term = Terminal::new(TestBackend::new(40, 12)).unwrap();
+        term.draw(|f| f.render_widget(view.clone(), f.area()))
+            .unwrap();
+        insta::assert_snapshot!(term.backend());
+    }
+}
@@ -0,0 +1,164 @@
+//! Parsing for the front matter at the start of a detail's markdown.
+//!
+//! Only flat key/value pairs are supported, which covers the fields a detail
+//! would carry like dates, tags and links, so a full YAML or TOML parser isn't
+//! needed at runtime. Values that are lists are split into their items.
+
+use pulldown_cmark::MetadataBlockKind;
+
+/// A field from the front matter of a detail
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field {
+    pub key: String,
+    /// A single value or each item of a list
+    pub values: Vec<String>,
+}
+
+/// Parse the contents of a `---` (YAML) or `+++` (TOML) delimited block
+pub fn parse(source: &str, kind: MetadataBlockKind) -> Vec<Field> {
+    match kind {
+        MetadataBlockKind::YamlStyle => parse_yaml(source),
+        MetadataBlockKind::PlusesStyle => parse_toml(source),
+    }
+}
+
+fn parse_yaml(source: &str) -> Vec<Field> {
+    let mut ret: Vec<Field> = Vec::new();
+    for line in source.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        // the items of a block list belong to the key above them
+        if let Some(item) = trimmed.strip_prefix("- ") {
+            if let Some(field) = ret.last_mut() {
+                field.values.push(unquote(item).to_string());
+            }
+            continue;
+        }
+        let Some((key, value)) = trimmed.split_once(':') else {
+            continue;
+        };
+        ret.push(Field {
+            key: key.trim().to_string(),
+            values: values(value),
+        });
+    }
+    ret
+}
+
+fn parse_toml(source: &str) -> Vec<Field> {
+    let mut ret = Vec::new();
+    let mut lines = source.lines();
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim();
+        // table headers are skipped, their keys are treated as top level
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with('[') {
+            continue;
+        }
+        let Some((key, value)) = trimmed.split_once('=') else {
+            continue;
+        };
+        let mut value = value.trim().to_string();
+        // an array can span multiple lines
+        if value.starts_with('[') {
+            while !value.ends_with(']') {
+                let Some(next) = lines.next() else {
+                    break;
+                };
+                value.push_str(next.trim());
+            }
+        }
+        ret.push(Field {
+            key: unquote(key).to_string(),
+            values: values(&value),
+        });
+    }
+    ret
+}
+
+/// Split a `[a, b]` list into its items, anything else is a single value
+fn values(value: &str) -> Vec<String> {
+    let value = value.trim();
+    let Some(items) = value
+        .strip_prefix('[')
+        .and_then(|rest| rest.strip_suffix(']'))
+    else {
+        return if value.is_empty() {
+            Vec::new()
+        } else {
+            vec![unquote(value).to_string()]
+        };
+    };
+    items
+        .split(',')
+        .map(unquote)
+        .filter(|item| !item.is_empty())
+        .map(ToString::to_string)
+        .collect()
+}
+
+fn unquote(value: &str) -> &str {
+    let value = value.trim();
+    for quote in ['"', '\''] {
+        if let Some(inner) = value
+            .strip_prefix(quote)
+            .and_then(|rest| rest.strip_suffix(quote))
+        {
+            return inner;
+        }
+    }
+    value
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn field(key: &str, values: &[&str]) -> Field {
+        Field {
+            key: key.to_string(),
+            values: values.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    #[test]
+    fn parse_yaml_front_matter() {
+        let source = r#"date: 2021-03
+tags: [rust, "lua"]
+# a comment
+links:
+  - https://example.com
+  - 'https://example.org'
+"#;
+        assert_eq!(
+            parse(source, MetadataBlockKind::YamlStyle),
+            vec![
+                field("date", &["2021-03"]),
+                field("tags", &["rust", "lua"]),
+                field("links", &["https://example.com", "https://example.org"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_toml_front_matter() {
+        let source = r#"date = "2021-03"
+tags = [
+    "rust",
+    "lua",
+]
+
+[extra]
+link = "https://example.com"
+"#;
+        assert_eq!(
+            parse(source, MetadataBlockKind::PlusesStyle),
+            vec![
+                field("date", &["2021-03"]),
+                field("tags", &["rust", "lua"]),
+                field("link", &["https://example.com"]),
+            ]
+        );
+    }
+}
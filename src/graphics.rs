@@ -0,0 +1,455 @@
+//! Images drawn with the Kitty or Sixel terminal graphics protocols.
+//!
+//! Images are always converted to half block character art so they can be
+//! displayed in any terminal. When the terminal supports a graphics protocol
+//! the views register where that art was drawn with the app's [`Registry`]
+//! and [`Graphics`] draws the image over it as the frame is written to the
+//! terminal.
+
+use std::{
+    fmt::Write,
+    io,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use ratatui::{
+    backend::{Backend, ClearType, WindowSize},
+    buffer::{Buffer, Cell},
+    layout::{Position, Rect, Size},
+};
+
+use crate::{clipboard::base64, data::Image};
+
+/// A terminal graphics protocol
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    /// The Kitty graphics protocol, also supported by WezTerm and Ghostty
+    Kitty,
+    /// DEC Sixel graphics, supported by foot, mlterm and xterm's VT340 mode
+    Sixel,
+}
+
+/// Where character art for an image is drawn in converted markdown
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Placement {
+    pub image: &'static Image,
+    /// The index of the first line of the art
+    pub line: usize,
+    /// The column the art starts at
+    pub column: usize,
+    /// The size of the art in character cells
+    pub columns: usize,
+    pub rows: usize,
+}
+
+/// The size in pixels of a character cell when the terminal doesn't report
+/// its size in pixels
+pub(crate) const DEFAULT_CELL_SIZE: (u16, u16) = (10, 20);
+
+/// The most base64 encoded data Kitty accepts in a single escape sequence
+const KITTY_CHUNK: usize = 4096;
+
+/// An image drawn over `area`, `cell` is the buffer's top left cell which
+/// carries the escape sequence to the terminal
+#[derive(Debug, Clone)]
+struct Drawn {
+    area: Rect,
+    image: &'static Image,
+    cell: Cell,
+}
+
+/// The images drawn in the current frame. Clones share the same images, the
+/// [`App`](crate::App) hands a clone to each view that displays images and
+/// the backend writing the frame holds another.
+#[derive(Debug, Clone, Default)]
+pub struct Registry {
+    protocol: Option<Protocol>,
+    images: Arc<Mutex<Vec<Drawn>>>,
+}
+
+impl Registry {
+    /// Draw images with `protocol` over their character art, this should
+    /// only be set when the terminal supports the protocol and its backend
+    /// is wrapped in [`Graphics`]
+    pub fn new(protocol: Option<Protocol>) -> Self {
+        Self {
+            protocol,
+            images: Arc::default(),
+        }
+    }
+
+    pub fn protocol(&self) -> Option<Protocol> {
+        self.protocol
+    }
+
+    /// Forget the images from the previous frame
+    pub(crate) fn clear(&self) {
+        self.images().clear();
+    }
+
+    /// Draw `image` over `area` once the frame in `buf` is written
+    pub(crate) fn register(&self, buf: &Buffer, area: Rect, image: &'static Image) {
+        if self.protocol.is_none() || area.intersection(buf.area) != area || area.is_empty() {
+            return;
+        }
+        let cell = buf[area.as_position()].clone();
+        self.images().push(Drawn { area, image, cell });
+    }
+
+    fn images(&self) -> MutexGuard<'_, Vec<Drawn>> {
+        self.images.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// A [`Backend`] that draws the registered images
+#[derive(Debug)]
+pub struct Graphics<B> {
+    inner: B,
+    registry: Registry,
+    /// The area and path of the images drawn by the last frame, they are
+    /// only drawn again when they move
+    shown: Vec<(Rect, &'static str)>,
+}
+
+impl<B> Graphics<B> {
+    /// Draw the images registered with `registry`, this should be the
+    /// registry the [`App`](crate::App) drawn to the terminal uses
+    pub fn new(inner: B, registry: Registry) -> Self {
+        Self {
+            inner,
+            registry,
+            shown: Vec::new(),
+        }
+    }
+}
+
+impl<B: Backend> Graphics<B> {
+    /// Append a cell to `cells` for each registered image that draws the
+    /// image after writing the cell's symbol, the cursor is saved and
+    /// restored around the image so the rest of the frame is unaffected.
+    /// Kitty images from the previous frame are removed first.
+    fn push_images(&mut self, cells: &mut Vec<(u16, u16, Cell)>) {
+        let Some(protocol) = self.registry.protocol else {
+            return;
+        };
+        let images = self.registry.images().clone();
+        let shown: Vec<_> = images.iter().map(|i| (i.area, i.image.path)).collect();
+        if shown == self.shown {
+            return;
+        }
+        let cell_size = self
+            .inner
+            .window_size()
+            .ok()
+            .filter(|size| size.pixels.width > 0 && size.columns_rows.width > 0)
+            .map(|size| {
+                (
+                    size.pixels.width / size.columns_rows.width,
+                    size.pixels.height / size.columns_rows.height.max(1),
+                )
+            })
+            .unwrap_or(DEFAULT_CELL_SIZE);
+        let mut clear = protocol.clear();
+        if let Some((_, _, cell)) = cells.first_mut() {
+            cell.set_symbol(&format!("{clear}{}", cell.symbol()));
+            clear = "";
+        }
+        for Drawn { area, image, cell } in images {
+            let graphic = protocol.encode(image, area.width, area.height, cell_size);
+            let symbol = format!(
+                "{clear}{}\x1b7\x1b[{};{}H{graphic}\x1b8",
+                cell.symbol(),
+                area.y + 1,
+                area.x + 1
+            );
+            clear = "";
+            let mut cell = cell;
+            cell.set_symbol(&symbol);
+            cells.push((area.x, area.y, cell));
+        }
+        if clear.is_empty() {
+            self.shown = shown;
+        }
+    }
+}
+
+impl<B: Backend> Backend for Graphics<B> {
+    fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        let mut cells: Vec<(u16, u16, Cell)> =
+            content.map(|(x, y, cell)| (x, y, cell.clone())).collect();
+        self.push_images(&mut cells);
+        self.inner
+            .draw(cells.iter().map(|(x, y, cell)| (*x, *y, cell)))
+    }
+
+    fn append_lines(&mut self, n: u16) -> io::Result<()> {
+        self.inner.append_lines(n)
+    }
+
+    fn hide_cursor(&mut self) -> io::Result<()> {
+        self.inner.hide_cursor()
+    }
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        self.inner.show_cursor()
+    }
+
+    fn get_cursor_position(&mut self) -> io::Result<Position> {
+        self.inner.get_cursor_position()
+    }
+
+    fn set_cursor_position<P: Into<Position>>(&mut self, position: P) -> io::Result<()> {
+        self.inner.set_cursor_position(position)
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        // every cell is written again after a clear, the images are removed
+        // and drawn again along with them
+        self.shown.clear();
+        self.inner.clear()
+    }
+
+    fn clear_region(&mut self, clear_type: ClearType) -> io::Result<()> {
+        self.inner.clear_region(clear_type)
+    }
+
+    fn size(&self) -> io::Result<Size> {
+        self.inner.size()
+    }
+
+    fn window_size(&mut self) -> io::Result<WindowSize> {
+        self.inner.window_size()
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl Protocol {
+    /// The escape sequence that removes every image this protocol drew,
+    /// Sixel images are part of the text so they are replaced as the cells
+    /// under them are redrawn
+    pub(crate) fn clear(self) -> &'static str {
+        match self {
+            Self::Kitty => "\x1b_Ga=d,d=A,q=2\x1b\\",
+            Self::Sixel => "",
+        }
+    }
+
+    /// The escape sequence drawing `image` over `columns` x `rows` cells
+    /// starting at the cursor, `cell_size` is the width and height of a cell
+    /// in pixels
+    pub(crate) fn encode(
+        self,
+        image: &Image,
+        columns: u16,
+        rows: u16,
+        cell_size: (u16, u16),
+    ) -> String {
+        match self {
+            Self::Kitty => kitty(image, columns, rows),
+            Self::Sixel => {
+                let (width, height) = cell_size;
+                sixel(
+                    image,
+                    columns as usize * width as usize,
+                    rows as usize * height as usize,
+                )
+            }
+        }
+    }
+}
+
+/// Transmit and display the image's pixels as RGBA, scaled by the terminal
+/// to fill the cells. Responses are suppressed so they aren't read as input
+/// and the cursor is left where it was.
+fn kitty(image: &Image, columns: u16, rows: u16) -> String {
+    let rgba: Vec<u8> = image
+        .pixels
+        .iter()
+        .flat_map(|pixel| match pixel {
+            Some([r, g, b]) => [*r, *g, *b, 255],
+            None => [0; 4],
+        })
+        .collect();
+    let data = base64(&rgba);
+    let mut chunks = data.as_bytes().chunks(KITTY_CHUNK).peekable();
+    let mut ret = format!(
+        "\x1b_Ga=T,f=32,s={},v={},c={columns},r={rows},C=1,q=2,",
+        image.width,
+        image.height()
+    );
+    while let Some(chunk) = chunks.next() {
+        let more = u8::from(chunks.peek().is_some());
+        // chunks are split from base64 so they are always ascii
+        let chunk = std::str::from_utf8(chunk).unwrap_or_default();
+        let _ = write!(ret, "m={more};{chunk}\x1b\\");
+        if more == 1 {
+            ret.push_str("\x1b_G");
+        }
+    }
+    ret
+}
+
+/// Draw the image scaled to `width` x `height` pixels, colors are reduced to
+/// a 6x6x6 cube and transparent pixels are left as they were
+fn sixel(image: &Image, width: usize, height: usize) -> String {
+    let register = |x: usize, y: usize| -> Option<usize> {
+        let x = x * image.width / width.max(1);
+        let y = y * image.height() / height.max(1);
+        let [r, g, b] = image.pixels.get(y * image.width + x).copied().flatten()?;
+        let level = |c: u8| (c as usize * 5 + 127) / 255;
+        Some(level(r) * 36 + level(g) * 6 + level(b))
+    };
+    let mut used = [false; 216];
+    for y in 0..height {
+        for x in 0..width {
+            if let Some(idx) = register(x, y) {
+                used[idx] = true;
+            }
+        }
+    }
+    let mut ret = format!("\x1bP0;1;0q\"1;1;{width};{height}");
+    for idx in (0..used.len()).filter(|idx| used[*idx]) {
+        let percent = |level: usize| level * 100 / 5;
+        let _ = write!(
+            ret,
+            "#{idx};2;{};{};{}",
+            percent(idx / 36),
+            percent(idx / 6 % 6),
+            percent(idx % 6)
+        );
+    }
+    for band in (0..height).step_by(6) {
+        let mut colors: Vec<usize> = (0..width)
+            .flat_map(|x| (band..(band + 6).min(height)).filter_map(move |y| register(x, y)))
+            .collect();
+        colors.sort_unstable();
+        colors.dedup();
+        for (n, color) in colors.iter().enumerate() {
+            if n > 0 {
+                ret.push('$');
+            }
+            let _ = write!(ret, "#{color}");
+            let sixels = (0..width).map(|x| {
+                let bits = (0..6)
+                    .filter(|bit| band + bit < height && register(x, band + bit) == Some(*color))
+                    .fold(0, |bits, bit| bits | 1 << bit);
+                char::from(b'?' + bits)
+            });
+            push_runs(&mut ret, sixels);
+        }
+        ret.push('-');
+    }
+    ret.push_str("\x1b\\");
+    ret
+}
+
+/// Write `sixels` using the repeat introducer for runs longer than 3
+fn push_runs(out: &mut String, sixels: impl Iterator<Item = char>) {
+    let mut sixels = sixels.peekable();
+    while let Some(sixel) = sixels.next() {
+        let mut count = 1;
+        while sixels.next_if_eq(&sixel).is_some() {
+            count += 1;
+        }
+        if count > 3 {
+            let _ = write!(out, "!{count}{sixel}");
+        } else {
+            out.extend(std::iter::repeat_n(sixel, count));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ratatui::{backend::TestBackend, style::Style, Terminal};
+
+    use super::*;
+
+    const RED: Option<[u8; 3]> = Some([255, 0, 0]);
+    const BLUE: Option<[u8; 3]> = Some([0, 0, 255]);
+
+    #[test]
+    fn kitty_chunks() {
+        let image = Image {
+            path: "test.png",
+            width: 2,
+            pixels: &[RED, None],
+        };
+        assert_eq!(
+            Protocol::Kitty.encode(&image, 2, 1, DEFAULT_CELL_SIZE),
+            "\x1b_Ga=T,f=32,s=2,v=1,c=2,r=1,C=1,q=2,m=0;/wAA/wAAAAA=\x1b\\"
+        );
+        static PIXELS: [Option<[u8; 3]>; 40 * 80] = [RED; 40 * 80];
+        let image = Image {
+            path: "large.png",
+            width: 40,
+            pixels: &PIXELS,
+        };
+        let encoded = Protocol::Kitty.encode(&image, 40, 40, DEFAULT_CELL_SIZE);
+        let sequences: Vec<&str> = encoded.split_terminator("\x1b\\").collect();
+        // 12800 bytes of rgba is 17068 characters of base64
+        assert_eq!(sequences.len(), 5);
+        assert!(sequences[0].starts_with("\x1b_Ga=T,f=32,s=40,v=80,c=40,r=40,C=1,q=2,m=1;"));
+        assert!(sequences[1..4].iter().all(|s| s.starts_with("\x1b_Gm=1;")));
+        assert!(sequences[4].starts_with("\x1b_Gm=0;"));
+    }
+
+    #[test]
+    fn sixel_bands() {
+        let image = Image {
+            path: "test.png",
+            width: 2,
+            pixels: &[RED, None, BLUE, BLUE],
+        };
+        // each pixel is scaled to 2x4 so there are two bands of six
+        assert_eq!(
+            Protocol::Sixel.encode(&image, 2, 2, (2, 4)),
+            "\x1bP0;1;0q\"1;1;4;8#5;2;0;0;100#180;2;100;0;0\
+             #5!4o$#180NN??-#5!4B-\x1b\\"
+        );
+    }
+
+    #[test]
+    fn draw_images_once() {
+        static IMAGE: Image = Image {
+            path: "test.png",
+            width: 1,
+            pixels: &[Some([255, 0, 0])],
+        };
+        let registry = Registry::new(Some(Protocol::Kitty));
+        let backend = Graphics::new(TestBackend::new(4, 2), registry.clone());
+        let mut terminal = Terminal::new(backend).unwrap();
+        let draw = |terminal: &mut Terminal<Graphics<TestBackend>>, x: u16| {
+            // forget what the last frame wrote to see what this one writes
+            Backend::clear(&mut terminal.backend_mut().inner).unwrap();
+            terminal
+                .draw(|frame| {
+                    registry.clear();
+                    frame.buffer_mut().set_string(0, 0, "ab", Style::new());
+                    registry.register(frame.buffer_mut(), Rect::new(x, 0, 1, 1), &IMAGE);
+                })
+                .unwrap();
+            terminal.backend().inner.buffer()[(x, 0)]
+                .symbol()
+                .to_string()
+        };
+        let graphic = Protocol::Kitty.encode(&IMAGE, 1, 1, DEFAULT_CELL_SIZE);
+        assert_eq!(
+            draw(&mut terminal, 1),
+            format!("b\x1b7\x1b[1;2H{graphic}\x1b8")
+        );
+        // an image that hasn't moved isn't sent again
+        assert_eq!(draw(&mut terminal, 1), " ");
+        // nothing else changed so the image that moved removes the old one
+        assert_eq!(
+            draw(&mut terminal, 2),
+            format!("{} \x1b7\x1b[1;3H{graphic}\x1b8", Protocol::Kitty.clear())
+        );
+    }
+}
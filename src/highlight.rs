@@ -0,0 +1,275 @@
+//! A small syntax highlighter for the languages that show up in details.
+//!
+//! Each line is split into comments, strings, numbers, keywords and function
+//! names which is enough to make a snippet readable without pulling in a
+//! full grammar for every language.
+
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span},
+};
+
+use crate::theme::Theme;
+
+struct Syntax {
+    keywords: &'static [&'static str],
+    line_comment: &'static str,
+    block_comment: (&'static str, &'static str),
+    /// Characters that start and end a string
+    quotes: &'static [char],
+}
+
+const RUST: Syntax = Syntax {
+    keywords: &[
+        "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+        "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move",
+        "mut", "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait",
+        "true", "type", "unsafe", "use", "where", "while",
+    ],
+    line_comment: "//",
+    block_comment: ("/*", "*/"),
+    // `'` is handled separately since it is also used for lifetimes
+    quotes: &['"'],
+};
+
+const LUA: Syntax = Syntax {
+    keywords: &[
+        "and", "break", "do", "else", "elseif", "end", "false", "for", "function", "goto", "if",
+        "in", "local", "nil", "not", "or", "repeat", "return", "then", "true", "until", "while",
+    ],
+    line_comment: "--",
+    block_comment: ("--[[", "]]"),
+    quotes: &['"', '\''],
+};
+
+const JS: Syntax = Syntax {
+    keywords: &[
+        "async",
+        "await",
+        "break",
+        "case",
+        "catch",
+        "class",
+        "const",
+        "continue",
+        "default",
+        "delete",
+        "do",
+        "else",
+        "export",
+        "extends",
+        "false",
+        "finally",
+        "for",
+        "from",
+        "function",
+        "if",
+        "import",
+        "in",
+        "instanceof",
+        "interface",
+        "let",
+        "new",
+        "null",
+        "of",
+        "return",
+        "static",
+        "switch",
+        "this",
+        "throw",
+        "true",
+        "try",
+        "type",
+        "typeof",
+        "undefined",
+        "var",
+        "void",
+        "while",
+        "yield",
+    ],
+    line_comment: "//",
+    block_comment: ("/*", "*/"),
+    quotes: &['"', '\'', '`'],
+};
+
+fn syntax(lang: &str) -> Option<&'static Syntax> {
+    match lang.trim().to_lowercase().as_str() {
+        "rust" | "rs" => Some(&RUST),
+        "lua" | "luau" => Some(&LUA),
+        "js" | "javascript" | "ts" | "typescript" | "jsx" | "tsx" => Some(&JS),
+        _ => None,
+    }
+}
+
+/// Highlight `code` written in `lang`, unknown languages are returned as
+/// plain lines in the theme's code color
+pub(crate) fn highlight(lang: &str, code: &str, theme: &Theme) -> Vec<Line<'static>> {
+    let plain = Style::new().fg(theme.code);
+    let Some(syntax) = syntax(lang) else {
+        return code
+            .lines()
+            .map(|line| Line::from(Span::styled(line.to_string(), plain)))
+            .collect();
+    };
+    let is_rust = std::ptr::eq(syntax, &RUST);
+    let mut in_block_comment = false;
+    let mut lines = Vec::new();
+    for line in code.lines() {
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        let mut push = |text: &str, color: Color| {
+            let style = Style::new().fg(color);
+            match spans.last_mut() {
+                Some(last) if last.style == style => last.content.to_mut().push_str(text),
+                _ => spans.push(Span::styled(text.to_string(), style)),
+            }
+        };
+        let mut rest = line;
+        while !rest.is_empty() {
+            let (open, close) = syntax.block_comment;
+            if in_block_comment {
+                let end = rest.find(close).map(|idx| idx + close.len());
+                in_block_comment = end.is_none();
+                let (comment, after) = rest.split_at(end.unwrap_or(rest.len()));
+                push(comment, theme.comment);
+                rest = after;
+                continue;
+            }
+            if rest.starts_with(open) {
+                in_block_comment = true;
+                push(open, theme.comment);
+                rest = &rest[open.len()..];
+                continue;
+            }
+            if rest.starts_with(syntax.line_comment) {
+                push(rest, theme.comment);
+                break;
+            }
+            let ch = rest.chars().next().unwrap_or_default();
+            let len = if syntax.quotes.contains(&ch) {
+                let len = string_len(rest, ch);
+                push(&rest[..len], theme.string);
+                len
+            } else if is_rust && ch == '\'' && char_literal_len(rest).is_some() {
+                let len = char_literal_len(rest).unwrap_or(1);
+                push(&rest[..len], theme.string);
+                len
+            } else if ch.is_ascii_digit() {
+                let len = word_len(rest);
+                push(&rest[..len], theme.number);
+                len
+            } else if ch.is_alphabetic() || ch == '_' {
+                let len = word_len(rest);
+                let word = &rest[..len];
+                let color = if syntax.keywords.contains(&word) {
+                    theme.keyword
+                } else if rest[len..].starts_with('(') || rest[len..].starts_with("!(") {
+                    theme.function
+                } else {
+                    theme.code
+                };
+                push(word, color);
+                len
+            } else {
+                let len = ch.len_utf8();
+                push(&rest[..len], theme.code);
+                len
+            };
+            rest = &rest[len..];
+        }
+        lines.push(Line::from(spans));
+    }
+    lines
+}
+
+/// The length of the identifier or number at the start of `s`
+fn word_len(s: &str) -> usize {
+    s.find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .unwrap_or(s.len())
+}
+
+/// The length of the string starting with `quote` at the start of `s`, an
+/// unterminated string runs to the end of the line
+fn string_len(s: &str, quote: char) -> usize {
+    let mut escaped = false;
+    for (idx, ch) in s.char_indices().skip(1) {
+        if escaped {
+            escaped = false;
+        } else if ch == '\\' {
+            escaped = true;
+        } else if ch == quote {
+            return idx + ch.len_utf8();
+        }
+    }
+    s.len()
+}
+
+/// The length of a rust char literal like `'a'` or `'\n'` at the start of
+/// `s`, `None` for a lifetime
+fn char_literal_len(s: &str) -> Option<usize> {
+    let mut chars = s.char_indices().skip(1);
+    let (_, first) = chars.next()?;
+    if first == '\\' {
+        // skip the escaped character so `'\''` is handled, `\u{..}` escapes
+        // run to the closing quote
+        let (idx, escaped) = chars.next()?;
+        let after = idx + escaped.len_utf8();
+        return s[after..].find('\'').map(|end| after + end + 1);
+    }
+    let (idx, close) = chars.next()?;
+    (close == '\'').then_some(idx + 1)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn tokens(lang: &str, code: &str) -> Vec<(String, Color)> {
+        highlight(lang, code, &Theme::DEFAULT)
+            .into_iter()
+            .flat_map(|line| line.spans)
+            .map(|span| (span.content.to_string(), span.style.fg.unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn highlight_rust() {
+        let theme = Theme::DEFAULT;
+        assert_eq!(
+            tokens(
+                "rust",
+                r#"fn main<'a>() { let c = '\n'; println!("hi {}", 1); } // done"#
+            ),
+            vec![
+                ("fn".to_string(), theme.keyword),
+                (" main<'a>() { ".to_string(), theme.code),
+                ("let".to_string(), theme.keyword),
+                (" c = ".to_string(), theme.code),
+                ("'\\n'".to_string(), theme.string),
+                ("; ".to_string(), theme.code),
+                ("println".to_string(), theme.function),
+                ("!(".to_string(), theme.code),
+                ("\"hi {}\"".to_string(), theme.string),
+                (", ".to_string(), theme.code),
+                ("1".to_string(), theme.number),
+                ("); } ".to_string(), theme.code),
+                ("// done".to_string(), theme.comment),
+            ]
+        );
+    }
+
+    #[test]
+    fn highlight_lua_block_comment() {
+        let theme = Theme::DEFAULT;
+        assert_eq!(
+            tokens("lua", "--[[ a\nb ]] local x = 'y'"),
+            vec![
+                ("--[[ a".to_string(), theme.comment),
+                ("b ]]".to_string(), theme.comment),
+                (" ".to_string(), theme.code),
+                ("local".to_string(), theme.keyword),
+                (" x = ".to_string(), theme.code),
+                ("'y'".to_string(), theme.string),
+            ]
+        );
+    }
+}
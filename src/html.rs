@@ -0,0 +1,180 @@
+//! Splitting the HTML that appears in markdown into text and tags.
+//!
+//! Only enough of HTML is understood to pick out tags, comments and
+//! character references, the markdown converter decides which tags are
+//! displayed and drops the rest.
+
+/// A piece of HTML
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Token {
+    /// Text with character references decoded
+    Text(String),
+    /// An opening or self closing tag, the name is lowercase
+    Open {
+        name: String,
+        attrs: String,
+    },
+    Close(String),
+}
+
+/// Markdown delivers an HTML block a line at a time so the tokenizer keeps
+/// track of a comment that is still open at the end of a line
+#[derive(Debug, Default)]
+pub(crate) struct Tokenizer {
+    in_comment: bool,
+}
+
+impl Tokenizer {
+    pub fn tokens(&mut self, html: &str) -> Vec<Token> {
+        let mut ret = Vec::new();
+        let mut rest = html;
+        while !rest.is_empty() {
+            if self.in_comment {
+                let Some(end) = rest.find("-->") else {
+                    break;
+                };
+                rest = &rest[end + 3..];
+                self.in_comment = false;
+                continue;
+            }
+            if let Some(after) = rest.strip_prefix("<!--") {
+                self.in_comment = true;
+                rest = after;
+                continue;
+            }
+            let tag = rest
+                .strip_prefix('<')
+                .and_then(|after| Some((after, after.find('>')?)));
+            let Some((after, end)) = tag else {
+                // text runs until the next thing that looks like a tag
+                let first = rest.chars().next().map_or(1, char::len_utf8);
+                let len = rest[first..]
+                    .find('<')
+                    .map(|idx| idx + first)
+                    .unwrap_or(rest.len());
+                ret.push(Token::Text(decode(&rest[..len])));
+                rest = &rest[len..];
+                continue;
+            };
+            let inner = after[..end].trim().trim_end_matches('/');
+            rest = &after[end + 1..];
+            if let Some(name) = inner.strip_prefix('/') {
+                ret.push(Token::Close(name.trim().to_lowercase()));
+                continue;
+            }
+            // doctypes and processing instructions are dropped
+            if inner.starts_with('!') || inner.starts_with('?') {
+                continue;
+            }
+            let (name, attrs) = inner.split_once(char::is_whitespace).unwrap_or((inner, ""));
+            ret.push(Token::Open {
+                name: name.to_lowercase(),
+                attrs: attrs.trim().to_string(),
+            });
+        }
+        ret
+    }
+}
+
+/// If an attribute is present, e.g. `open` in `<details open>`
+pub(crate) fn has_attr(attrs: &str, name: &str) -> bool {
+    attrs.split_whitespace().any(|attr| {
+        attr.split_once('=')
+            .map(|(key, _)| key)
+            .unwrap_or(attr)
+            .eq_ignore_ascii_case(name)
+    })
+}
+
+/// Decode the character references that show up in prose, others are left
+/// as written
+fn decode(text: &str) -> String {
+    let mut ret = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        ret.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(end) = rest.find(';').filter(|end| *end <= 10) else {
+            ret.push('&');
+            rest = &rest[1..];
+            continue;
+        };
+        let decoded = match &rest[1..end] {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some('\u{a0}'),
+            "mdash" => Some('—'),
+            "ndash" => Some('–'),
+            "hellip" => Some('…'),
+            "copy" => Some('©'),
+            name => name
+                .strip_prefix("#x")
+                .or_else(|| name.strip_prefix("#X"))
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| name.strip_prefix('#')?.parse().ok())
+                .and_then(char::from_u32),
+        };
+        match decoded {
+            Some(ch) => {
+                ret.push(ch);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                ret.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    ret.push_str(rest);
+    ret
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn open(name: &str, attrs: &str) -> Token {
+        Token::Open {
+            name: name.to_string(),
+            attrs: attrs.to_string(),
+        }
+    }
+
+    #[test]
+    fn tokenize_html() {
+        let mut tokenizer = Tokenizer::default();
+        assert_eq!(
+            tokenizer.tokens(r#"<B class="x">a &amp; b</b><br/><!-- hidden"#),
+            vec![
+                open("b", r#"class="x""#),
+                Token::Text("a & b".to_string()),
+                Token::Close("b".to_string()),
+                open("br", ""),
+            ]
+        );
+        // the comment continues onto the next line
+        assert_eq!(
+            tokenizer.tokens("still hidden --><details open>1 < 2 &#x41;"),
+            vec![
+                open("details", "open"),
+                Token::Text("1 ".to_string()),
+                Token::Text("< 2 A".to_string()),
+            ]
+        );
+        // text can start with a character wider than one byte
+        assert_eq!(
+            tokenizer.tokens("é <i>ü</i>"),
+            vec![
+                Token::Text("é ".to_string()),
+                open("i", ""),
+                Token::Text("ü".to_string()),
+                Token::Close("i".to_string()),
+            ]
+        );
+        assert!(has_attr("class=\"a\" open", "open"));
+        assert!(!has_attr("opened", "open"));
+    }
+}
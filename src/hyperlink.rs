@@ -0,0 +1,162 @@
+//! Clickable links using the OSC 8 escape sequence.
+//!
+//! A ratatui [`Cell`] can't carry a link target and escape sequences in a
+//! cell's symbol break the width calculations used when diffing frames, so
+//! views register the cells a link is drawn in with the app's [`Registry`]
+//! while rendering and [`Hyperlinks`] wraps those cells in the escape
+//! sequence as they are written to the terminal.
+
+use std::{
+    collections::BTreeMap,
+    io,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use ratatui::{
+    backend::{Backend, ClearType, WindowSize},
+    buffer::{Buffer, Cell},
+    layout::{Position, Rect, Size},
+};
+
+/// The link target of each cell, keyed by column and row
+type Cells = BTreeMap<(u16, u16), Arc<str>>;
+
+/// The link target of each cell in the current frame. Clones share the same
+/// cells, the [`App`](crate::App) hands a clone to each view that displays
+/// links and the backend writing the frame holds another.
+#[derive(Debug, Clone, Default)]
+pub struct Registry {
+    enabled: bool,
+    cells: Arc<Mutex<Cells>>,
+}
+
+impl Registry {
+    /// When `enabled` is false markdown links are displayed as `[text](url)`,
+    /// this should only be enabled when the terminal supports OSC 8 and the
+    /// terminal's backend is wrapped in [`Hyperlinks`].
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            cells: Arc::default(),
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// The url the cell at `x`, `y` links to in the last frame rendered
+    pub fn url_at(&self, x: u16, y: u16) -> Option<Arc<str>> {
+        self.cells().get(&(x, y)).cloned()
+    }
+
+    /// Forget the links from the previous frame
+    pub(crate) fn clear(&self) {
+        self.cells().clear();
+    }
+
+    /// Link the cells at `positions` to `url`
+    pub(crate) fn register(&self, positions: impl IntoIterator<Item = Position>, url: &str) {
+        if !self.enabled {
+            return;
+        }
+        let url: Arc<str> = url.into();
+        let mut cells = self.cells();
+        for pos in positions {
+            cells.insert((pos.x, pos.y), url.clone());
+        }
+    }
+
+    /// Link every non-blank cell in `area` to `url`
+    pub(crate) fn register_area(&self, buf: &Buffer, area: Rect, url: &str) {
+        let area = area.intersection(buf.area);
+        self.register(area.positions().filter(|p| buf[*p].symbol() != " "), url);
+    }
+
+    fn cells(&self) -> MutexGuard<'_, Cells> {
+        self.cells.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Wrap `symbol` in the escape sequence linking it to `url`
+pub fn wrap(symbol: &str, url: &str) -> String {
+    format!("\x1b]8;;{url}\x1b\\{symbol}\x1b]8;;\x1b\\")
+}
+
+/// A [`Backend`] that writes the registered cells as hyperlinks
+#[derive(Debug)]
+pub struct Hyperlinks<B> {
+    inner: B,
+    registry: Registry,
+}
+
+impl<B> Hyperlinks<B> {
+    /// Write the cells registered with `registry` as hyperlinks, this should
+    /// be the registry the [`App`](crate::App) drawn to the terminal uses
+    pub fn new(inner: B, registry: Registry) -> Self {
+        Self { inner, registry }
+    }
+}
+
+impl<B: Backend> Backend for Hyperlinks<B> {
+    fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        let cells: Vec<(u16, u16, Cell)> = {
+            let links = self.registry.cells();
+            content
+                .map(|(x, y, cell)| {
+                    let mut cell = cell.clone();
+                    if let Some(url) = links.get(&(x, y)) {
+                        let symbol = wrap(cell.symbol(), url);
+                        cell.set_symbol(&symbol);
+                    }
+                    (x, y, cell)
+                })
+                .collect()
+        };
+        self.inner
+            .draw(cells.iter().map(|(x, y, cell)| (*x, *y, cell)))
+    }
+
+    fn append_lines(&mut self, n: u16) -> io::Result<()> {
+        self.inner.append_lines(n)
+    }
+
+    fn hide_cursor(&mut self) -> io::Result<()> {
+        self.inner.hide_cursor()
+    }
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        self.inner.show_cursor()
+    }
+
+    fn get_cursor_position(&mut self) -> io::Result<Position> {
+        self.inner.get_cursor_position()
+    }
+
+    fn set_cursor_position<P: Into<Position>>(&mut self, position: P) -> io::Result<()> {
+        self.inner.set_cursor_position(position)
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        self.inner.clear()
+    }
+
+    fn clear_region(&mut self, clear_type: ClearType) -> io::Result<()> {
+        self.inner.clear_region(clear_type)
+    }
+
+    fn size(&self) -> io::Result<Size> {
+        self.inner.size()
+    }
+
+    fn window_size(&mut self) -> io::Result<WindowSize> {
+        self.inner.window_size()
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
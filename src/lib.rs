@@ -4,22 +4,64 @@ use ratatui::{
     widgets::{block::Title, *},
 };
 
+pub mod cast;
+mod certifications;
+pub mod clipboard;
+mod contact;
 mod data;
+mod date;
 mod detail_view;
 mod edu;
+mod front_matter;
+pub mod graphics;
+mod highlight;
+mod html;
+pub mod hyperlink;
 mod list_state;
 mod markdown;
+mod math;
 mod oss;
+mod publications;
+pub mod route;
+pub mod script;
+mod section;
+pub mod theme;
+mod timeline;
 mod work;
 
-pub use data::source::DATABASE;
+pub use data::{
+    source::DATABASE, Certification, Contact, Database, Detail, Entry, Profile, Project,
+    Publication, Role, School, Section, Talk, Workplace,
+};
+pub use date::Date;
 
 const DEFAULT_STYLE: Style = Style::new().fg(Color::Green).bg(Color::Black);
 
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct App<'a> {
+    db: &'a Database<'a>,
     main_menu_state: ListState,
-    sub_page: Option<Page<'a>>,
+    pages: Vec<Box<dyn Page + 'a>>,
+    sub_page: Option<usize>,
+    /// The focused link in the Home page footer
+    home_link: Option<usize>,
+    action: Option<Action>,
+    status: Option<String>,
+    context: Context,
+}
+
+/// The registries and theme an [`App`] shares with its pages, the pages that
+/// display markdown hold a clone and register what they draw with it
+#[derive(Debug, Clone, Default)]
+pub struct Context {
+    /// Where links are registered when displayed as OSC 8 hyperlinks, see the
+    /// [`hyperlink`] module
+    pub hyperlinks: hyperlink::Registry,
+    /// Where images are registered when drawn with a terminal graphics
+    /// protocol, see the [`graphics`] module
+    pub images: graphics::Registry,
+    /// The colors of highlighted code and headings in markdown
+    pub theme: theme::Theme,
 }
 
 pub trait Navigable {
@@ -27,45 +69,138 @@ pub trait Navigable {
     fn decrement_selection(&mut self);
     fn handle_enter(&mut self);
     fn handle_left(&mut self) -> bool;
+    /// Move the focus to the next link, after the last link nothing is
+    /// focused. Views without links can ignore this.
+    fn focus_next_link(&mut self) {}
+    /// Scroll to the next heading, or the previous one when `forward` is
+    /// false. Views without headings can ignore this.
+    fn jump_to_heading(&mut self, _forward: bool) {}
+    /// Fit anything that depends on the size of the last render to that
+    /// size. Pages are cloned to render, so this is called on the page
+    /// itself first to keep the work between frames. Views without
+    /// markdown can ignore this.
+    fn fit_to_view(&mut self) {}
+    /// Select the `idx`th item from the top, used to open a [`route`]. The
+    /// default moves the selection down `idx` times so it should be
+    /// overridden by views that don't start at the top.
+    fn select_item(&mut self, idx: usize) {
+        for _ in 0..idx {
+            self.increment_selection();
+        }
+    }
+    /// Take the [`Action`] requested by the last event, if any
+    fn take_action(&mut self) -> Option<Action> {
+        None
+    }
+    /// The text copied to the clipboard when the viewer yanks the current
+    /// item, e.g. the markdown of a detail
+    fn yank(&self) -> Option<String> {
+        None
+    }
 }
 
-#[derive(Debug, Clone)]
-enum Page<'a> {
-    Work(work::WorkView<'a>),
-    Oss(oss::OssView<'a>),
-    Edu(edu::EduView),
+/// Something the app needs the host to do on its behalf, see
+/// [`App::take_action`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    /// Open a url in the browser
+    Open(String),
+    /// Copy text to the clipboard
+    Copy(String),
 }
 
-impl<'a> Navigable for Page<'a> {
-    fn increment_selection(&mut self) {
-        match self {
-            Page::Work(inner) => inner.increment_selection(),
-            Page::Oss(inner) => inner.increment_selection(),
-            Page::Edu(inner) => inner.increment_selection(),
-        }
+/// A page listed in the main menu, the built in Work, Open Source and
+/// Education pages are implemented with this trait and additional pages can
+/// be added with [`App::push_page`] or [`App::insert_page`].
+///
+/// Navigation events are only forwarded to a page while it is open, a page
+/// is closed when [`Navigable::handle_left`] returns `false`. Pages must be
+/// [`Clone`] so the [`App`] can be cloned.
+///
+/// ```
+/// use ratatui::{buffer::Buffer, layout::Rect, widgets::{Paragraph, Widget}};
+/// use resume_tui::{App, Navigable, Page};
+///
+/// #[derive(Clone)]
+/// struct Talks;
+///
+/// impl Navigable for Talks {
+///     fn increment_selection(&mut self) {}
+///     fn decrement_selection(&mut self) {}
+///     fn handle_enter(&mut self) {}
+///     fn handle_left(&mut self) -> bool {
+///         false
+///     }
+/// }
+///
+/// impl Page for Talks {
+///     fn title(&self) -> &str {
+///         "Talks"
+///     }
+///
+///     fn render(&mut self, area: Rect, buf: &mut Buffer) {
+///         Paragraph::new("RustConf 2024").render(area, buf);
+///     }
+/// }
+///
+/// let mut app = App::new();
+/// app.insert_page(1, Talks);
+/// app.open("talks").unwrap();
+/// ```
+pub trait Page: Navigable + ClonePage {
+    /// The name of the page in the main menu
+    fn title(&self) -> &str;
+    /// The name used to open this page with a [`route`], this defaults to
+    /// the title but the built in pages use a fixed id so their routes
+    /// don't depend on how they are titled.
+    fn id(&self) -> &str {
+        self.title()
     }
-    fn decrement_selection(&mut self) {
-        match self {
-            Page::Work(inner) => inner.decrement_selection(),
-            Page::Oss(inner) => inner.decrement_selection(),
-            Page::Edu(inner) => inner.decrement_selection(),
-        }
+    /// Render the page's content, the menu and page border are drawn by the
+    /// [`App`]
+    fn render(&mut self, area: Rect, buf: &mut Buffer);
+    /// Return to the initial state, this is called each time the page is
+    /// opened from the main menu
+    fn reset(&mut self) {}
+}
+
+/// Clone a boxed [`Page`], this is implemented for every page that is
+/// [`Clone`]
+pub trait ClonePage {
+    fn clone_page<'a>(&self) -> Box<dyn Page + 'a>
+    where
+        Self: 'a;
+}
+
+impl<T: Page + Clone> ClonePage for T {
+    fn clone_page<'a>(&self) -> Box<dyn Page + 'a>
+    where
+        Self: 'a,
+    {
+        Box::new(self.clone())
     }
+}
 
-    fn handle_enter(&mut self) {
-        match self {
-            Page::Work(inner) => inner.handle_enter(),
-            Page::Oss(inner) => inner.handle_enter(),
-            Page::Edu(inner) => inner.handle_enter(),
-        }
+impl<'a> Clone for Box<dyn Page + 'a> {
+    fn clone(&self) -> Self {
+        self.clone_page()
     }
+}
 
-    fn handle_left(&mut self) -> bool {
-        match self {
-            Page::Work(inner) => inner.handle_left(),
-            Page::Oss(inner) => inner.handle_left(),
-            Page::Edu(inner) => inner.handle_left(),
-        }
+impl<'a> std::fmt::Debug for App<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("App")
+            .field("db", &self.db)
+            .field("main_menu_state", &self.main_menu_state)
+            .field(
+                "pages",
+                &self.pages.iter().map(|p| p.title()).collect::<Vec<_>>(),
+            )
+            .field("sub_page", &self.sub_page)
+            .field("home_link", &self.home_link)
+            .field("action", &self.action)
+            .field("status", &self.status)
+            .finish()
     }
 }
 
@@ -76,45 +211,233 @@ impl<'a> Default for App<'a> {
 }
 
 impl<'a> App<'a> {
+    /// Create an app rendering the resume that was built into this crate
     pub fn new() -> Self {
-        Self {
-            main_menu_state: ListState::new(4),
+        Self::with_database(&DATABASE)
+    }
+
+    /// Create an app rendering the provided resume, the resume can borrow
+    /// data loaded at runtime for as long as the app is in use
+    ///
+    /// ```
+    /// use resume_tui::{App, Contact, Database, Date, Project};
+    ///
+    /// let name = String::from("Jane Doe");
+    /// let long_desc = format!("Widgets for **{}**", "everyone");
+    /// let projects = vec![Project {
+    ///     name: "widgets",
+    ///     short_desc: "A widget library",
+    ///     long_desc: &long_desc,
+    ///     start: Some(Date::new(2021, 6)),
+    ///     end: None,
+    ///     sub_projects: &[],
+    /// }];
+    /// let resume = Database {
+    ///     name: &name,
+    ///     tag_line: "I make things",
+    ///     contact: Contact {
+    ///         email: Some("jane@example.com"),
+    ///         website: None,
+    ///         phone: None,
+    ///         location: None,
+    ///         profiles: &[],
+    ///     },
+    ///     jobs: &[],
+    ///     open_source: &projects,
+    ///     education: &[],
+    ///     publications: &[],
+    ///     talks: &[],
+    ///     certifications: &[],
+    ///     sections: &[],
+    /// };
+    /// let app = App::with_database(&resume);
+    /// ```
+    pub fn with_database(db: &'a Database<'a>) -> Self {
+        Self::with_context(db, Context::default())
+    }
+
+    /// Create an app rendering the provided resume with its links registered
+    /// in `hyperlinks`, see the [`hyperlink`] module
+    pub fn with_hyperlinks(db: &'a Database<'a>, hyperlinks: hyperlink::Registry) -> Self {
+        Self::with_context(
+            db,
+            Context {
+                hyperlinks,
+                ..Context::default()
+            },
+        )
+    }
+
+    /// Create an app rendering the provided resume with its code blocks and
+    /// headings colored with `theme`, see the [`theme`] module
+    pub fn with_theme(db: &'a Database<'a>, theme: theme::Theme) -> Self {
+        Self::with_context(
+            db,
+            Context {
+                theme,
+                ..Context::default()
+            },
+        )
+    }
+
+    /// Create an app rendering the provided resume with its links and images
+    /// registered in `context`
+    pub fn with_context(db: &'a Database<'a>, context: Context) -> Self {
+        let mut ret = Self {
+            db,
+            main_menu_state: ListState::new(0),
+            pages: Vec::new(),
             sub_page: None,
+            home_link: None,
+            action: None,
+            status: None,
+            context: context.clone(),
+        };
+        ret.push_page(work::WorkView::new(db.jobs, context.clone()));
+        ret.push_page(oss::OssView::new(db.open_source, context.clone()));
+        ret.push_page(edu::EduView::new(db.education));
+        if !timeline::bars(db).is_empty() {
+            ret.push_page(timeline::TimelineView::new(db, context.clone()));
+        }
+        if !db.publications.is_empty() {
+            ret.push_page(publications::CitationsView::new(
+                db.name,
+                db.publications,
+                context.clone(),
+            ));
+        }
+        if !db.talks.is_empty() {
+            ret.push_page(publications::CitationsView::new(
+                db.name,
+                db.talks,
+                context.clone(),
+            ));
+        }
+        if !db.certifications.is_empty() {
+            ret.push_page(certifications::CertificationsView::new(db.certifications));
+        }
+        if !db.contact.is_empty() {
+            ret.push_page(contact::ContactView::new(&db.contact));
+        }
+        for section in db.sections {
+            ret.push_page(section::SectionView::new(section, context.clone()));
+        }
+        ret
+    }
+
+    /// The registry the app's links are registered in, a page that displays
+    /// links can register them here
+    pub fn hyperlinks(&self) -> &hyperlink::Registry {
+        &self.context.hyperlinks
+    }
+
+    /// Add a page to the end of the main menu
+    pub fn push_page(&mut self, page: impl Page + 'a) {
+        self.insert_page(self.pages.len(), page);
+    }
+
+    /// Add a page to the main menu at `idx`, where 0 is the entry directly
+    /// below Home. `idx` is clamped to the number of pages.
+    pub fn insert_page(&mut self, idx: usize, page: impl Page + 'a) {
+        let idx = idx.min(self.pages.len());
+        self.pages.insert(idx, Box::new(page));
+        self.sub_page = None;
+        self.main_menu_state = ListState::new(self.pages.len());
+    }
+
+    /// Reset the app and navigate to the page described by `route`, see the
+    /// [`route`] module for the supported syntax.
+    pub fn open(&mut self, route: &str) -> Result<(), Error> {
+        let ids: Vec<&str> = self.pages.iter().map(|p| p.id()).collect();
+        let mut path = route::resolve(self.db, &ids, route)?.into_iter();
+        self.sub_page = None;
+        self.home_link = None;
+        self.main_menu_state = ListState::new(self.pages.len());
+        let Some(idx) = path.next() else {
+            return Ok(());
+        };
+        // The first entry in the main menu is always Home
+        self.main_menu_state.select(idx + 1);
+        self.handle_right();
+        for idx in path {
+            if let Some(page) = self.current_page() {
+                page.select_item(idx);
+                page.handle_enter();
+            }
+        }
+        Ok(())
+    }
+
+    /// Navigate to `route` like [`App::open`], an unknown route opens the
+    /// Home page instead and displays the error in the status line
+    pub fn open_or_home(&mut self, route: &str) {
+        if let Err(e) = self.open(route) {
+            // the empty route always resolves to Home
+            let _ = self.open("");
+            self.set_status(e.to_string());
         }
     }
+
     pub fn tick(&mut self, terminal: &mut Terminal<impl Backend>) -> Result<(), Error> {
         self.draw(terminal)?;
         Ok(())
     }
 
     pub fn event(&mut self, event: Event) -> Result<(), Error> {
+        self.status = None;
         match event {
             Event::Up => self.decrement_selection(),
             Event::Down => self.increment_selection(),
             Event::Left => self.handle_left(),
             Event::Right => self.handle_right(),
+            Event::Tab => self.focus_next_link(),
+            Event::Yank => self.action = self.yank().map(Action::Copy),
+            Event::NextHeading => self.jump_to_heading(true),
+            Event::PrevHeading => self.jump_to_heading(false),
             Event::Quit => return Err(Error::Exit),
         }
+        if let Some(action) = self.current_page().and_then(|p| p.take_action()) {
+            self.action = Some(action);
+        }
         Ok(())
     }
 
+    /// Take the [`Action`] requested by the last event, the host should
+    /// perform it, e.g. opening a link when Enter is pressed on it.
+    pub fn take_action(&mut self) -> Option<Action> {
+        self.action.take()
+    }
+
+    /// Display a short message at the bottom of the page until the next
+    /// event, e.g. to confirm a link was copied.
+    pub fn set_status(&mut self, status: impl Into<String>) {
+        self.status = Some(status.into());
+    }
+
     fn draw(&mut self, terminal: &mut Terminal<impl Backend>) -> std::io::Result<()> {
         terminal.draw(|f| f.render_widget(self, f.area()))?;
         Ok(())
     }
 
-    fn get_selected_menu_name(&self) -> &'static str {
-        Self::menu_name(self.main_menu_state.selected().unwrap_or(255))
+    fn get_selected_menu_name(&self) -> &str {
+        self.menu_name(self.main_menu_state.selected().unwrap_or(usize::MAX))
     }
 
-    fn menu_name(idx: usize) -> &'static str {
-        match idx {
-            0 => "Home",
-            1 => "Work",
-            2 => "Open Source",
-            3 => "Education",
-            _ => "???",
+    fn menu_name(&self, idx: usize) -> &str {
+        if idx == 0 {
+            return "Home";
         }
+        self.pages.get(idx - 1).map(|p| p.title()).unwrap_or("???")
+    }
+
+    /// The width needed to display the longest menu entry and the left border
+    fn menu_width(&self) -> u16 {
+        let longest = (0..=self.pages.len())
+            .map(|idx| Span::raw(self.menu_name(idx)).width())
+            .max()
+            .unwrap_or_default()
+            .max("Menu".len() + 2);
+        longest as u16 + 1
     }
 
     fn render_menu(&mut self, area: Rect, buf: &mut Buffer) {
@@ -126,12 +449,10 @@ impl<'a> App<'a> {
             .style(DEFAULT_STYLE);
         let content_area = block.inner(area);
         block.render(area, buf);
-        let list = List::new([
-            ListItem::from("Home"),
-            "Work".into(),
-            "Open Source".into(),
-            "Education".into(),
-        ]);
+        let items: Vec<ListItem> = (0..=self.pages.len())
+            .map(|idx| ListItem::from(self.menu_name(idx).to_string()))
+            .collect();
+        let list = List::new(items);
         let list = if self.sub_page.is_some() {
             list.style(Style::new().add_modifier(Modifier::DIM))
                 .highlight_style(DEFAULT_STYLE.bold().remove_modifier(Modifier::DIM))
@@ -145,9 +466,9 @@ impl<'a> App<'a> {
         let title = if self.sub_page.is_none() {
             "Home".to_string()
         } else {
-            format!("{} - {}", DATABASE.name, self.get_selected_menu_name())
+            format!("{} - {}", self.db.name, self.get_selected_menu_name())
         };
-        let total_area = Block::bordered()
+        let mut total_area = Block::bordered()
             .title(Title::from(title))
             .title_alignment(Alignment::Center)
             .style(DEFAULT_STYLE)
@@ -157,49 +478,106 @@ impl<'a> App<'a> {
                 ..symbols::border::PLAIN
             })
             .padding(Padding::ZERO);
+        if let Some(status) = &self.status {
+            total_area = total_area.title_bottom(Line::from(status.as_str()).centered());
+        }
         let inner_rect = total_area.inner(area);
         total_area.render(area, buf);
-        let Some(sub_page) = self.sub_page.clone() else {
+        let Some(page) = self.sub_page.and_then(|idx| self.pages.get_mut(idx)) else {
             self.render_home(inner_rect, buf);
             return;
         };
-        match sub_page {
-            Page::Work(work_state) => work_state.render(inner_rect, buf),
-            Page::Oss(inner) => inner.render(inner_rect, buf),
-            Page::Edu(inner) => inner.render(inner_rect, buf),
-        }
+        page.render(inner_rect, buf);
+    }
+
+    fn current_page(&mut self) -> Option<&mut Box<dyn Page + 'a>> {
+        self.sub_page.and_then(|idx| self.pages.get_mut(idx))
     }
 
     fn render_home(&self, area: Rect, buf: &mut Buffer) {
         let layout = Layout::vertical(Constraint::from_percentages([45, 15, 40, 5]))
             .flex(layout::Flex::Center);
-        let [_, content_area, _, footer] = layout.areas(area);
+        let [_, content_area, experience_area, footer] = layout.areas(area);
 
-        Paragraph::new(vec![DATABASE.name.bold().into(), DATABASE.tag_line.into()])
+        Paragraph::new(vec![self.db.name.bold().into(), self.db.tag_line.into()])
             .alignment(Alignment::Center)
             .render(content_area, buf);
-        let foot_layout = Layout::horizontal(Constraint::from_percentages([50, 50]));
-        let [lhs, rhs] = foot_layout.areas(footer);
-        Paragraph::new(
-            DATABASE
-                .github
-                .map(|gh| format!(" https://github.com/{gh}"))
-                .unwrap_or_default(),
-        )
-        .alignment(Alignment::Left)
-        .render(lhs, buf);
-        Paragraph::new(
-            DATABASE
-                .github
-                .map(|li| format!("https://www.linkedin.com/in/{li} "))
-                .unwrap_or_default(),
-        )
-        .alignment(Alignment::Right)
-        .render(rhs, buf);
+        if !self.db.jobs.is_empty() {
+            let today = data::today();
+            let total = work::tenure(self.db.experience(today));
+            let tags: Vec<_> = self
+                .db
+                .tag_experience(today)
+                .into_iter()
+                .map(|(tag, months)| format!("{tag} {}", work::tenure(months)))
+                .collect();
+            Paragraph::new(vec![
+                format!("{total} of experience").into(),
+                Line::default(),
+                tags.join(" · ").into(),
+            ])
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .render(experience_area.inner(Margin::new(area.width / 8, 0)), buf);
+        }
+        // spread the contact details across the footer, the first is aligned
+        // to the left edge, the last to the right edge and any others centered
+        let links = self.db.contact.links();
+        let cells = Layout::horizontal(links.iter().map(|_| Constraint::Fill(1))).split(footer);
+        for (idx, ((link, url), cell)) in links.iter().zip(cells.iter()).enumerate() {
+            let style = if self.home_link == Some(idx) {
+                Style::new().add_modifier(Modifier::REVERSED)
+            } else {
+                Style::new()
+            };
+            let link = Span::styled(link.as_str(), style);
+            let (alignment, link) = if idx == 0 {
+                (Alignment::Left, Line::from(vec![" ".into(), link]))
+            } else if idx + 1 == links.len() {
+                (Alignment::Right, Line::from(vec![link, " ".into()]))
+            } else {
+                (Alignment::Center, Line::from(link))
+            };
+            Paragraph::new(link).alignment(alignment).render(*cell, buf);
+            if let Some(url) = url {
+                self.context.hyperlinks.register_area(buf, *cell, url);
+            }
+        }
+    }
+
+    /// The text for the current page's item or on the Home page the focused
+    /// link, falling back to the email address
+    fn yank(&self) -> Option<String> {
+        if let Some(page) = self.sub_page.and_then(|idx| self.pages.get(idx)) {
+            return page.yank();
+        }
+        let links = self.db.contact.links();
+        if let Some((text, _)) = self.home_link.and_then(|idx| links.get(idx)) {
+            return Some(text.clone());
+        }
+        self.db.contact.email.map(str::to_string)
+    }
+
+    fn focus_next_link(&mut self) {
+        if let Some(page) = self.current_page() {
+            page.focus_next_link();
+            return;
+        }
+        // plain text profiles can't be opened so they are skipped
+        let links = self.db.contact.links();
+        let start = self.home_link.map_or(0, |idx| idx + 1);
+        self.home_link = (start..links.len()).find(|idx| links[*idx].1.is_some());
+    }
+
+    fn jump_to_heading(&mut self, forward: bool) {
+        if let Some(page) = self.current_page() {
+            page.jump_to_heading(forward);
+        }
     }
 
     fn increment_selection(&mut self) {
-        let Some(sub_page) = self.sub_page.as_mut() else {
+        let Some(sub_page) = self.current_page() else {
+            self.home_link = None;
             self.main_menu_state.increment();
             return;
         };
@@ -207,7 +585,8 @@ impl<'a> App<'a> {
     }
 
     fn decrement_selection(&mut self) {
-        let Some(sub_page) = self.sub_page.as_mut() else {
+        let Some(sub_page) = self.current_page() else {
+            self.home_link = None;
             self.main_menu_state.decrement();
             return;
         };
@@ -215,34 +594,50 @@ impl<'a> App<'a> {
     }
 
     fn handle_left(&mut self) {
-        if let Some(mut page) = self.sub_page.take() {
-            if page.handle_left() {
-                self.sub_page = Some(page);
+        if let Some(page) = self.current_page() {
+            if !page.handle_left() {
+                self.sub_page = None;
             }
         }
     }
 
     fn handle_right(&mut self) {
-        if let Some(sub_page) = self.sub_page.as_mut() {
+        if let Some(sub_page) = self.current_page() {
             sub_page.handle_enter();
             return;
         };
+        if let Some(idx) = self.home_link {
+            self.action = self
+                .db
+                .contact
+                .links()
+                .into_iter()
+                .nth(idx)
+                .and_then(|(_, url)| url)
+                .map(Action::Open);
+            return;
+        }
         let Some(selected) = self.main_menu_state.selected() else {
             return;
         };
-        self.sub_page = match selected {
-            0 => None,
-            1 => Some(Page::Work(Default::default())),
-            2 => Some(Page::Oss(Default::default())),
-            3 => Some(Page::Edu(Default::default())),
-            _ => return,
-        }
+        let Some(idx) = selected.checked_sub(1) else {
+            self.sub_page = None;
+            return;
+        };
+        let Some(page) = self.pages.get_mut(idx) else {
+            return;
+        };
+        page.reset();
+        self.home_link = None;
+        self.sub_page = Some(idx);
     }
 }
 
 impl<'a> Widget for &mut App<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let full = Layout::horizontal([Constraint::Length(12), Constraint::Min(1)]);
+        self.context.hyperlinks.clear();
+        self.context.images.clear();
+        let full = Layout::horizontal([Constraint::Length(self.menu_width()), Constraint::Min(1)]);
         let [menu_area, display_area] = full.areas(area);
 
         self.render_menu(menu_area, buf);
@@ -250,18 +645,52 @@ impl<'a> Widget for &mut App<'a> {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Event {
     Up,
     Down,
     Left,
     Right,
+    /// Focus the next link
+    Tab,
+    /// Copy the current item to the clipboard
+    Yank,
+    /// Scroll to the next heading in a detail
+    NextHeading,
+    /// Scroll to the previous heading in a detail
+    PrevHeading,
     Quit,
 }
 
+impl std::str::FromStr for Event {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "up" => Self::Up,
+            "down" => Self::Down,
+            "left" => Self::Left,
+            "right" => Self::Right,
+            "tab" => Self::Tab,
+            "yank" => Self::Yank,
+            "next-heading" => Self::NextHeading,
+            "prev-heading" => Self::PrevHeading,
+            "quit" => Self::Quit,
+            _ => return Err(Error::UnknownEvent(s.to_string())),
+        })
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("{0}")]
     Io(#[from] std::io::Error),
     #[error("Exit")]
     Exit,
+    #[error("Unknown event: {0}")]
+    UnknownEvent(String),
+    #[error("Invalid route: {0}")]
+    Route(String),
+    #[error("Invalid script, line {line}: {msg}")]
+    Script { line: usize, msg: String },
 }
@@ -3,14 +3,23 @@ use ratatui::widgets::ListState;
 #[derive(Debug, Clone)]
 pub struct ListStateWrapper {
     max: usize,
+    /// Rows that can't be selected, e.g. the headings between groups of items
+    skip: Vec<usize>,
     state: ListState,
 }
 
 impl ListStateWrapper {
     pub fn new(max: usize) -> Self {
+        Self::with_skipped(max, Vec::new())
+    }
+
+    /// A list whose selection moves over the rows in `skip`
+    pub fn with_skipped(max: usize, skip: Vec<usize>) -> Self {
+        let first = (0..=max).find(|idx| !skip.contains(idx)).unwrap_or(0);
         Self {
             max,
-            state: ListState::default().with_selected(Some(0)),
+            skip,
+            state: ListState::default().with_selected(Some(first)),
         }
     }
 
@@ -19,7 +28,9 @@ impl ListStateWrapper {
             self.select(0);
             return;
         };
-        let next = (existing + 1).min(self.max);
+        let next = (existing + 1..=self.max)
+            .find(|idx| !self.skip.contains(idx))
+            .unwrap_or(existing);
         self.select(next);
     }
 
@@ -28,10 +39,24 @@ impl ListStateWrapper {
             self.state.select(Some(self.max));
             return;
         };
-        let next = existing.saturating_sub(1);
+        let next = (0..existing)
+            .rev()
+            .find(|idx| !self.skip.contains(idx))
+            .unwrap_or(existing);
         self.select(next);
     }
 
+    /// Select the `item`th row that isn't skipped, the same row that moving
+    /// down `item` times from the top would select
+    pub fn select_item(&mut self, item: usize) {
+        let rows: Vec<usize> = (0..=self.max)
+            .filter(|idx| !self.skip.contains(idx))
+            .collect();
+        if let Some(&row) = rows.get(item).or(rows.last()) {
+            self.select(row);
+        }
+    }
+
     pub fn selected(&self) -> Option<usize> {
         self.state.selected()
     }
@@ -1,48 +1,507 @@
-use color_eyre::config::HookBuilder;
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::PathBuf,
+    process::{Command, Stdio},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use color_eyre::{config::HookBuilder, eyre::eyre};
 use crossterm::{
-    event::{self, Event as TermEvent, KeyCode, KeyEventKind, KeyModifiers},
+    event::{self, Event as TermEvent, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
     terminal::{EnterAlternateScreen, LeaveAlternateScreen},
     ExecutableCommand,
 };
 use ratatui::{
     backend::{Backend, CrosstermBackend},
-    Terminal,
+    layout::Rect,
+    Terminal, TerminalOptions, Viewport,
+};
+use resume_tui::{
+    cast::{Recorder, Resizes},
+    clipboard,
+    graphics::{self, Graphics, Protocol},
+    hyperlink::{self, Hyperlinks},
+    script::{self, Step},
+    Action, App, Context, Error, Event, DATABASE,
 };
-use resume_tui::{App, Error, Event};
+
+const USAGE: &str = "\
+Usage: resume-tui [--open <ROUTE>] [--record <FILE>] [--demo [--dwell <MS>] [--idle <SECS>]]
+                  [--hyperlinks | --no-hyperlinks] [--graphics <PROTOCOL>]
+       resume-tui demo [--script <FILE>] [--record <FILE>] [--size <COLS>x<ROWS>]
+
+Options:
+  --open <ROUTE>    Start on the page described by ROUTE, for example
+                    `work/<company>/<detail>`, `oss/<project>/<sub-project>`
+                    or `edu`
+  --record <FILE>   Write the session to FILE as an asciicast v2 recording
+  --demo            Tour every page automatically until a key is pressed, the
+                    tour restarts once no keys have been pressed for a while
+  --dwell <MS>      How long the tour stays on each page [default: 2000]
+  --idle <SECS>     How long to wait for input before restarting the tour
+                    [default: 30]
+  --hyperlinks      Display links as clickable OSC 8 hyperlinks, by default
+                    these are used when the terminal is known to support them
+  --no-hyperlinks   Display links as `[text](url)`
+  --graphics <PROTOCOL>
+                    Draw images with `kitty` or `sixel` graphics, or only as
+                    character art with `none`, by default a protocol is used
+                    when the terminal is known to support it. Graphics aren't
+                    written to recordings.
+  --script <FILE>   Replay the events in FILE instead of the built in script
+  --size <COLS>x<ROWS>
+                    The terminal size used when recording a demo [default: 100x30]
+  -h, --help        Print this message
+
+When `demo` is combined with `--record` the script is rendered without a
+terminal and the cast is written as fast as possible.
+
+Keys:
+  Up/Down, j/k      Move the selection or scroll
+  Right/Enter, l    Open the selected item, the focused link, footnote or summary
+  Left, h           Go back
+  Tab               Focus the next link, footnote or summary
+  y                 Copy the current item to the clipboard
+  ] / [             Jump to the next or previous heading in a detail
+  q, Esc            Quit";
+
+#[derive(Debug, Default)]
+struct Args {
+    demo: bool,
+    kiosk: bool,
+    dwell: Option<Duration>,
+    idle: Option<Duration>,
+    hyperlinks: Option<bool>,
+    graphics: Option<Option<Protocol>>,
+    open: Option<String>,
+    record: Option<PathBuf>,
+    script: Option<PathBuf>,
+    size: Option<(u16, u16)>,
+}
+
+impl Args {
+    fn parse() -> color_eyre::Result<Self> {
+        let mut ret = Self::default();
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "demo" => ret.demo = true,
+                "--demo" => ret.kiosk = true,
+                "--dwell" => {
+                    let dwell = next_value(&mut args, &arg)?;
+                    let ms = dwell
+                        .parse()
+                        .map_err(|_| eyre!("invalid dwell time `{dwell}`\n\n{USAGE}"))?;
+                    ret.dwell = Some(Duration::from_millis(ms));
+                }
+                "--idle" => {
+                    let idle = next_value(&mut args, &arg)?;
+                    let secs = idle
+                        .parse()
+                        .map_err(|_| eyre!("invalid idle time `{idle}`\n\n{USAGE}"))?;
+                    ret.idle = Some(Duration::from_secs(secs));
+                }
+                "--hyperlinks" => ret.hyperlinks = Some(true),
+                "--no-hyperlinks" => ret.hyperlinks = Some(false),
+                "--graphics" => {
+                    let protocol = next_value(&mut args, &arg)?;
+                    ret.graphics = Some(match protocol.as_str() {
+                        "kitty" => Some(Protocol::Kitty),
+                        "sixel" => Some(Protocol::Sixel),
+                        "none" => None,
+                        _ => {
+                            return Err(eyre!("invalid graphics protocol `{protocol}`\n\n{USAGE}"))
+                        }
+                    });
+                }
+                "--open" => ret.open = Some(next_value(&mut args, &arg)?),
+                "--record" => ret.record = Some(next_value(&mut args, &arg)?.into()),
+                "--script" => ret.script = Some(next_value(&mut args, &arg)?.into()),
+                "--size" => {
+                    let size = next_value(&mut args, &arg)?;
+                    let (cols, rows) = size
+                        .split_once('x')
+                        .and_then(|(c, r)| Some((c.parse().ok()?, r.parse().ok()?)))
+                        .ok_or_else(|| eyre!("invalid size `{size}`\n\n{USAGE}"))?;
+                    ret.size = Some((cols, rows));
+                }
+                "-h" | "--help" => {
+                    println!("{USAGE}");
+                    std::process::exit(0);
+                }
+                _ => return Err(eyre!("unexpected argument `{arg}`\n\n{USAGE}")),
+            }
+        }
+        Ok(ret)
+    }
+}
+
+fn next_value(args: &mut impl Iterator<Item = String>, flag: &str) -> color_eyre::Result<String> {
+    args.next()
+        .ok_or_else(|| eyre!("`{flag}` requires a value\n\n{USAGE}"))
+}
 
 fn main() -> color_eyre::Result<()> {
     #[cfg(feature = "logging")]
     env_logger::init();
-    // setup terminal
+    let args = Args::parse()?;
+    // a recording can't replay the graphics so only the character art is used
+    let graphics = args
+        .graphics
+        .unwrap_or_else(supported_graphics)
+        .filter(|_| args.record.is_none());
+    let context = Context {
+        hyperlinks: hyperlink::Registry::new(args.hyperlinks.unwrap_or_else(supports_hyperlinks)),
+        images: graphics::Registry::new(graphics),
+        ..Context::default()
+    };
+    if args.demo {
+        let script = if let Some(path) = &args.script {
+            std::fs::read_to_string(path)?
+        } else {
+            script::DEFAULT_SCRIPT.to_string()
+        };
+        let steps = script::parse(&script)?;
+        if let Some(path) = &args.record {
+            return record_demo(path, &steps, args.size.unwrap_or((100, 30)));
+        }
+        return with_terminal(None, context.clone(), |terminal, resizes| {
+            play_demo(terminal, resizes, &context, &steps)
+        });
+    }
+    if args.kiosk {
+        let app = App::with_context(&DATABASE, context.clone());
+        let steps = script::tour(&app, args.dwell.unwrap_or(Duration::from_millis(2000)));
+        let idle = args.idle.unwrap_or(Duration::from_secs(30));
+        return with_terminal(args.record.as_ref(), context, |terminal, resizes| {
+            kiosk(terminal, resizes, app, &steps, idle)
+        });
+    }
+    let mut app = App::with_context(&DATABASE, context.clone());
+    if let Some(route) = &args.open {
+        app.open(route)?;
+    } else if let Some(route) = std::env::var("SSH_ORIGINAL_COMMAND")
+        .ok()
+        .map(|command| command.trim().to_string())
+        .filter(|command| !command.is_empty())
+    {
+        // when run as the command for an SSH server, `ssh host <route>`
+        // arrives in the environment instead of the arguments and a typo
+        // shouldn't end the session before it starts
+        app.open_or_home(&route);
+    }
+    with_terminal(args.record.as_ref(), context, |terminal, resizes| {
+        run(terminal, resizes, app)
+    })
+}
+
+/// Best effort detection of terminals known to support OSC 8 hyperlinks,
+/// multiplexers are excluded since they may not pass the sequences through
+fn supports_hyperlinks() -> bool {
+    let var = |name| std::env::var(name).unwrap_or_default();
+    if std::env::var_os("TMUX").is_some() || var("TERM").starts_with("screen") {
+        return false;
+    }
+    let version = |name| var(name).parse::<u32>().unwrap_or_default();
+    matches!(
+        var("TERM_PROGRAM").as_str(),
+        "iTerm.app" | "WezTerm" | "vscode" | "ghostty" | "Hyper"
+    ) || ["kitty", "alacritty", "foot", "ghostty"]
+        .iter()
+        .any(|term| var("TERM").contains(term))
+        || std::env::var_os("WT_SESSION").is_some()
+        || version("VTE_VERSION") >= 5000
+        || version("KONSOLE_VERSION") >= 201200
+}
+
+/// Best effort detection of terminals known to support a graphics protocol,
+/// multiplexers are excluded since they may not pass the sequences through
+fn supported_graphics() -> Option<Protocol> {
+    let var = |name| std::env::var(name).unwrap_or_default();
+    if std::env::var_os("TMUX").is_some() || var("TERM").starts_with("screen") {
+        return None;
+    }
+    if var("TERM").contains("kitty")
+        || std::env::var_os("KITTY_WINDOW_ID").is_some()
+        || matches!(var("TERM_PROGRAM").as_str(), "WezTerm" | "ghostty")
+    {
+        return Some(Protocol::Kitty);
+    }
+    ["foot", "mlterm", "contour"]
+        .iter()
+        .any(|term| var("TERM").contains(term))
+        .then_some(Protocol::Sixel)
+}
+
+/// Setup the terminal, optionally recording everything written to it, and
+/// run `f` before restoring the terminal. `f` should send the terminal's
+/// resize events to the [`Resizes`] so they are included in the recording.
+fn with_terminal(
+    record: Option<&PathBuf>,
+    context: Context,
+    f: impl FnOnce(
+        &mut Terminal<Hyperlinks<Graphics<CrosstermBackend<Box<dyn Write>>>>>,
+        &Resizes,
+    ) -> color_eyre::Result<()>,
+) -> color_eyre::Result<()> {
     init_error_hooks()?;
-    let mut terminal = init_terminal()?;
-    let mut app = App::new();
+    let (cols, rows) = crossterm::terminal::size()?;
+    let recorder = record
+        .map(|path| -> color_eyre::Result<_> {
+            let file = BufWriter::new(File::create(path)?);
+            Ok(Recorder::new(std::io::stdout(), file, cols, rows)?)
+        })
+        .transpose()?;
+    let resizes = recorder.as_ref().map(Recorder::resizes).unwrap_or_default();
+    let writer: Box<dyn Write> = match recorder {
+        Some(recorder) => Box::new(recorder),
+        None => Box::new(std::io::stdout()),
+    };
+    let mut terminal = init_terminal(writer, context)?;
+    let res = f(&mut terminal, &resizes);
+    drop(terminal);
+    restore_terminal()?;
+    res
+}
+
+fn run(
+    terminal: &mut Terminal<impl Backend>,
+    resizes: &Resizes,
+    mut app: App,
+) -> color_eyre::Result<()> {
     loop {
-        app.tick(&mut terminal)?;
-        if let TermEvent::Key(key) = event::read()? {
-            if key.kind == KeyEventKind::Press {
-                let ev = match key.code {
-                    KeyCode::Char('q') | KeyCode::Esc => Event::Quit,
-                    KeyCode::Char('c') if key.modifiers == KeyModifiers::CONTROL => Event::Quit,
-                    KeyCode::Char('h') | KeyCode::Left => Event::Left,
-                    KeyCode::Char('j') | KeyCode::Down => Event::Down,
-                    KeyCode::Char('k') | KeyCode::Up => Event::Up,
-                    KeyCode::Char('l') | KeyCode::Right | KeyCode::Enter => Event::Right,
-                    _ => continue,
-                };
-                let res = app.event(ev);
-                if matches!(res, Err(Error::Exit)) {
-                    break;
+        app.tick(terminal)?;
+        if let Some(key) = read_key(resizes)? {
+            let Some(ev) = key_event(key) else {
+                continue;
+            };
+            let res = app.event(ev);
+            if matches!(res, Err(Error::Exit)) {
+                break;
+            }
+            res?;
+            handle_action(&mut app)?;
+        }
+    }
+    Ok(())
+}
+
+/// Carry out the action requested by the app, links are opened with the
+/// system opener unless this is an SSH session or no opener is available in
+/// which case they are copied to the clipboard with OSC 52.
+fn handle_action(app: &mut App) -> color_eyre::Result<()> {
+    let Some(action) = app.take_action() else {
+        return Ok(());
+    };
+    match action {
+        Action::Open(url) => {
+            if is_ssh() || open_url(&url).is_err() {
+                copy(&url)?;
+                app.set_status(format!(" Copied {url} to the clipboard "));
+            }
+        }
+        Action::Copy(text) => {
+            copy(&text)?;
+            app.set_status(" Copied to the clipboard ");
+        }
+    }
+    Ok(())
+}
+
+fn is_ssh() -> bool {
+    std::env::var_os("SSH_CONNECTION").is_some() || std::env::var_os("SSH_TTY").is_some()
+}
+
+fn open_url(url: &str) -> std::io::Result<()> {
+    let mut cmd = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", "start", ""]);
+        cmd
+    } else {
+        Command::new("xdg-open")
+    };
+    cmd.arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    Ok(())
+}
+
+/// Copy `text` to the clipboard of the terminal, this is written directly to
+/// stdout so it isn't included in recordings
+fn copy(text: &str) -> std::io::Result<()> {
+    let mut stdout = std::io::stdout();
+    stdout.write_all(clipboard::osc52(text).as_bytes())?;
+    stdout.flush()
+}
+
+/// Read the next terminal event, resizes are sent to `resizes` and only key
+/// events are returned
+fn read_key(resizes: &Resizes) -> std::io::Result<Option<KeyEvent>> {
+    match event::read()? {
+        TermEvent::Key(key) => Ok(Some(key)),
+        TermEvent::Resize(cols, rows) => {
+            resizes.resize(cols, rows);
+            Ok(None)
+        }
+        _ => Ok(None),
+    }
+}
+
+fn key_event(key: KeyEvent) -> Option<Event> {
+    if key.kind != KeyEventKind::Press {
+        return None;
+    }
+    Some(match key.code {
+        KeyCode::Char('q') | KeyCode::Esc => Event::Quit,
+        KeyCode::Char('c') if key.modifiers == KeyModifiers::CONTROL => Event::Quit,
+        KeyCode::Char('h') | KeyCode::Left => Event::Left,
+        KeyCode::Char('j') | KeyCode::Down => Event::Down,
+        KeyCode::Char('k') | KeyCode::Up => Event::Up,
+        KeyCode::Char('l') | KeyCode::Right | KeyCode::Enter => Event::Right,
+        KeyCode::Tab => Event::Tab,
+        KeyCode::Char('y') => Event::Yank,
+        KeyCode::Char(']') => Event::NextHeading,
+        KeyCode::Char('[') => Event::PrevHeading,
+        _ => return None,
+    })
+}
+
+/// Loop through the tour until a key is pressed, then hand control to the
+/// user until no keys have been pressed for `idle` at which point the tour
+/// starts over from the beginning with a copy of the initial `app`.
+fn kiosk(
+    terminal: &mut Terminal<impl Backend>,
+    resizes: &Resizes,
+    initial: App,
+    tour: &[Step],
+    idle: Duration,
+) -> color_eyre::Result<()> {
+    let Some(first) = tour.first() else {
+        return run(terminal, resizes, initial);
+    };
+    let mut app = initial.clone();
+    let mut idx = 0;
+    let mut next_step = Instant::now() + first.delay;
+    let mut last_input: Option<Instant> = None;
+    loop {
+        app.tick(terminal)?;
+        let deadline = last_input.map(|last| last + idle).unwrap_or(next_step);
+        if event::poll(deadline.saturating_duration_since(Instant::now()))? {
+            if let Some(key) = read_key(resizes)? {
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+                last_input = Some(Instant::now());
+                if let Some(ev) = key_event(key) {
+                    let res = app.event(ev);
+                    if matches!(res, Err(Error::Exit)) {
+                        break;
+                    }
+                    res?;
+                    handle_action(&mut app)?;
                 }
-                res?;
             }
+            continue;
+        }
+        if last_input.take().is_some() {
+            app = initial.clone();
+            idx = 0;
+            next_step = Instant::now() + first.delay;
+            continue;
+        }
+        if !apply_step(&mut app, &tour[idx])? {
+            break;
         }
+        idx = (idx + 1) % tour.len();
+        if idx == 0 {
+            app = initial.clone();
+        }
+        next_step = Instant::now() + tour[idx].delay;
     }
-    restore_terminal()?;
+    Ok(())
+}
+
+/// Play the demo script in the current terminal, any key press ends the demo
+fn play_demo(
+    terminal: &mut Terminal<impl Backend>,
+    resizes: &Resizes,
+    context: &Context,
+    steps: &[Step],
+) -> color_eyre::Result<()> {
+    let mut app = App::with_context(&DATABASE, context.clone());
+    app.tick(terminal)?;
+    for step in steps {
+        // other events, like a resize, redraw without cutting the step short
+        // so playback keeps the timing of a recording
+        let deadline = Instant::now() + step.delay;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() || !event::poll(remaining)? {
+                break;
+            }
+            match read_key(resizes)? {
+                Some(key) if key.kind == KeyEventKind::Press => return Ok(()),
+                Some(_) => {}
+                None => app.tick(terminal)?,
+            }
+        }
+        if !apply_step(&mut app, step)? {
+            break;
+        }
+        app.tick(terminal)?;
+    }
+    Ok(())
+}
 
+/// Render the demo script without a terminal, writing each frame to a cast
+/// file at the time the script says it should happen.
+fn record_demo(path: &PathBuf, steps: &[Step], (cols, rows): (u16, u16)) -> color_eyre::Result<()> {
+    let now = Arc::new(AtomicU64::new(0));
+    let clock = now.clone();
+    let file = BufWriter::new(File::create(path)?);
+    let recorder = Recorder::with_clock(std::io::sink(), file, cols, rows, move || {
+        Duration::from_micros(clock.load(Ordering::Relaxed))
+    })?;
+    let mut terminal = Terminal::with_options(
+        CrosstermBackend::new(recorder),
+        TerminalOptions {
+            viewport: Viewport::Fixed(Rect::new(0, 0, cols, rows)),
+        },
+    )?;
+    // hyperlinks are left disabled so the cast looks the same in every
+    // terminal it is played back in
+    let mut app = App::new();
+    app.tick(&mut terminal)?;
+    for step in steps {
+        now.fetch_add(step.delay.as_micros() as u64, Ordering::Relaxed);
+        if !apply_step(&mut app, step)? {
+            break;
+        }
+        app.tick(&mut terminal)?;
+    }
     Ok(())
 }
 
+/// Send the step's event to the app, returns false if the app should exit
+fn apply_step(app: &mut App, step: &Step) -> color_eyre::Result<bool> {
+    let Some(ev) = step.event else {
+        return Ok(true);
+    };
+    match app.event(ev) {
+        Err(Error::Exit) => Ok(false),
+        res => res.map(|_| true).map_err(Into::into),
+    }
+}
+
 fn init_error_hooks() -> color_eyre::Result<()> {
     let (panic, error) = HookBuilder::default().into_hooks();
     let panic = panic.into_panic_hook();
@@ -58,10 +517,14 @@ fn init_error_hooks() -> color_eyre::Result<()> {
     Ok(())
 }
 
-fn init_terminal() -> color_eyre::Result<Terminal<impl Backend>> {
+fn init_terminal<W: Write>(
+    writer: W,
+    context: Context,
+) -> color_eyre::Result<Terminal<Hyperlinks<Graphics<CrosstermBackend<W>>>>> {
     crossterm::terminal::enable_raw_mode()?;
     std::io::stdout().execute(EnterAlternateScreen)?;
-    let backend = CrosstermBackend::new(std::io::stdout());
+    let backend = Graphics::new(CrosstermBackend::new(writer), context.images);
+    let backend = Hyperlinks::new(backend, context.hyperlinks);
     let terminal = Terminal::new(backend)?;
     Ok(terminal)
 }
@@ -1,61 +1,163 @@
-use pulldown_cmark::{CodeBlockKind, Event, Parser, Tag, TagEnd};
+use std::ops::Range;
+
+use pulldown_cmark::{
+    Alignment, CodeBlockKind, Event, MetadataBlockKind, Options, Parser, Tag, TagEnd,
+};
 use ratatui::{
-    style::{Modifier, Style},
+    style::{Color, Modifier, Style},
     text::{Line, Span, Text},
 };
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    data::Image,
+    front_matter::{self, Field},
+    graphics::Placement,
+    highlight::highlight,
+    html::{self, Token},
+    math,
+    theme::Theme,
+};
+
+/// A link in converted markdown
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Link {
+    pub url: String,
+    /// The index of each line the link is displayed on and the spans in
+    /// that line used to display it, a link wrapped across lines has an
+    /// entry for each line
+    pub spans: Vec<(usize, Range<usize>)>,
+    /// For a footnote marker, the index of the link following it focuses:
+    /// the note for a reference and the first reference for a note
+    pub jump: Option<usize>,
+    /// For a `<details>` summary, the index of the section it expands or
+    /// collapses
+    pub toggle: Option<usize>,
+}
 
-pub fn convert_md(s: &'static str) -> Text<'static> {
-    convert(s).unwrap_or_else(|| {
-        log::debug!("Plain text!");
-        Text::raw(s)
-    })
+impl Link {
+    /// If following the link moves around the document instead of opening
+    /// its url
+    pub fn is_internal(&self) -> bool {
+        self.jump.is_some() || self.toggle.is_some()
+    }
+
+    /// The indexes of the lines the link is displayed on
+    pub fn lines(&self) -> Range<usize> {
+        let first = self
+            .spans
+            .first()
+            .map(|(line, _)| *line)
+            .unwrap_or_default();
+        let last = self.spans.last().map(|(line, _)| *line).unwrap_or(first);
+        first..last + 1
+    }
+}
+
+/// A heading in converted markdown
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Heading {
+    /// 1 through 6
+    pub level: u8,
+    pub text: String,
+    /// The index of the line the heading is displayed on
+    pub line: usize,
+}
+
+/// Converted markdown along with the position of the things in it that can
+/// be navigated to
+#[derive(Debug, Clone, Default)]
+pub struct Document {
+    pub text: Text<'static>,
+    pub links: Vec<Link>,
+    pub headings: Vec<Heading>,
+    /// The fields of the front matter, if there was any
+    pub metadata: Vec<Field>,
+    /// Where character art was drawn for images
+    pub(crate) images: Vec<Placement>,
+}
+
+pub fn convert_md(s: &str) -> Text<'static> {
+    convert_document(s, false, None).text
+}
+
+/// Convert markdown, also returning the position of each link and heading in
+/// the text. When `hyperlinks` is true links are displayed as underlined text
+/// without the url, the caller is responsible for registering them with the
+/// [`hyperlink`](crate::hyperlink) module. When `width` is provided text is
+/// wrapped between words and tables are shrunk to fit, so each line of the
+/// result is exactly one row.
+pub fn convert_document(s: &str, hyperlinks: bool, width: Option<usize>) -> Document {
+    convert_toggled(s, hyperlinks, width, &[], &Theme::DEFAULT)
 }
 
 const fn default_style() -> Style {
     crate::DEFAULT_STYLE
 }
 
-fn convert(s: &'static str) -> Option<Text<'static>> {
-    let parser = Parser::new(s);
+/// Convert markdown like [`convert_document`], the `<details>` sections at
+/// the indexes in `toggled` are displayed the opposite of their `open`
+/// attribute and code blocks and headings are colored with `theme`
+pub fn convert_toggled(
+    s: &str,
+    hyperlinks: bool,
+    width: Option<usize>,
+    toggled: &[usize],
+    theme: &Theme,
+) -> Document {
+    let parser = Parser::new_ext(
+        s,
+        Options::ENABLE_TABLES
+            | Options::ENABLE_TASKLISTS
+            | Options::ENABLE_FOOTNOTES
+            | Options::ENABLE_DEFINITION_LIST
+            | Options::ENABLE_YAML_STYLE_METADATA_BLOCKS
+            | Options::ENABLE_PLUSES_DELIMITED_METADATA_BLOCKS
+            | Options::ENABLE_MATH,
+    );
     let mut wrapper = Wrapper::new();
+    wrapper.hyperlinks = hyperlinks;
+    wrapper.width = width;
+    wrapper.toggled = toggled.to_vec();
+    wrapper.theme = *theme;
     for event in parser {
         log::trace!("Event: {event:#?}");
         match event {
             Event::Start(tag) => match tag {
                 Tag::Paragraph => {}
-                Tag::Heading { .. } => {
-                    wrapper.clear_style();
-                    wrapper.modify_style(Modifier::BOLD)
-                }
+                Tag::Heading { level, .. } => wrapper.start_heading(level as u8),
                 Tag::BlockQuote(_) => {
                     wrapper.line_prefix = Some(Span::raw("| ").style(default_style()));
                     wrapper.push_symbol("| ");
                 }
                 Tag::CodeBlock(kind) => {
-                    wrapper.push_text_with_style("```", default_style());
-                    if let CodeBlockKind::Fenced(name) = kind {
-                        wrapper.push_text_with_style(name, default_style())
-                    }
-                    wrapper.new_line();
+                    let lang = match kind {
+                        CodeBlockKind::Fenced(lang) => lang.to_string(),
+                        CodeBlockKind::Indented => String::new(),
+                    };
+                    wrapper.code = Some((lang, String::new()));
                 }
                 Tag::HtmlBlock => {}
-                Tag::List(idx) => {
-                    wrapper.list_number = idx;
+                Tag::List(number) => wrapper.start_list(number),
+                Tag::Item => wrapper.start_item(),
+                Tag::FootnoteDefinition(label) => wrapper.start_note(&label),
+                Tag::Table(alignments) => {
+                    wrapper.table = Some(Table {
+                        alignments,
+                        prefix: core::mem::take(&mut wrapper.line),
+                        ..Default::default()
+                    });
                 }
-                Tag::Item => {
-                    let ch = if let Some(i) = wrapper.list_number {
-                        wrapper.list_number = Some(i + 1);
-                        i.to_string()
-                    } else {
-                        '-'.to_string()
-                    };
-                    wrapper.push_symbol(ch);
+                Tag::TableHead | Tag::TableRow => {
+                    if let Some(table) = wrapper.table.as_mut() {
+                        table.rows.push(Vec::new());
+                    }
+                }
+                Tag::TableCell => {
+                    if wrapper.table.as_ref().is_some_and(|t| t.head_rows == 0) {
+                        wrapper.modify_style(Modifier::BOLD);
+                    }
                 }
-                Tag::FootnoteDefinition(_)
-                | Tag::Table(_)
-                | Tag::TableHead
-                | Tag::TableRow
-                | Tag::TableCell => {}
                 Tag::Emphasis => wrapper.modify_style(Modifier::ITALIC),
                 Tag::Strong => wrapper.modify_style(Modifier::BOLD),
                 Tag::Strikethrough => {
@@ -63,13 +165,33 @@ fn convert(s: &'static str) -> Option<Text<'static>> {
                 }
                 Tag::Link { dest_url, .. } => {
                     wrapper.link_url = Some(dest_url.to_string());
-                    wrapper.push_symbol('[');
+                    // links in tables are displayed with their url and can't
+                    // be focused since the cells are moved when laid out
+                    if wrapper.table.is_none() {
+                        wrapper.link_start = Some((wrapper.lines.len(), wrapper.line.len()));
+                    }
+                    if wrapper.hyperlinks && wrapper.table.is_none() {
+                        wrapper.modify_style(Modifier::UNDERLINED);
+                    } else {
+                        wrapper.push_symbol('[');
+                    }
+                }
+                Tag::Image { dest_url, .. } => {
+                    wrapper.image = Some((dest_url.to_string(), String::new()));
+                }
+                Tag::MetadataBlock(kind) => wrapper.metadata_block = Some((kind, String::new())),
+                Tag::DefinitionList => {
+                    if !wrapper.line_is_blank() {
+                        wrapper.new_line();
+                    }
+                }
+                Tag::DefinitionListTitle => {
+                    if !wrapper.line_is_blank() {
+                        wrapper.new_line();
+                    }
+                    wrapper.modify_style(Modifier::BOLD);
                 }
-                Tag::Image { .. } => return None,
-                Tag::MetadataBlock(_) => return None,
-                Tag::DefinitionList => return None,
-                Tag::DefinitionListTitle => return None,
-                Tag::DefinitionListDefinition => return None,
+                Tag::DefinitionListDefinition => wrapper.start_definition(),
             },
             Event::End(tag) => {
                 match tag {
@@ -77,31 +199,66 @@ fn convert(s: &'static str) -> Option<Text<'static>> {
                         wrapper.new_line();
                         wrapper.new_line();
                     }
-                    TagEnd::Heading(_)
-                    | TagEnd::HtmlBlock
-                    | TagEnd::List(_)
-                    | TagEnd::Item
-                    | TagEnd::FootnoteDefinition => {
+                    TagEnd::Heading(_) => wrapper.end_heading(),
+                    // a block of only tags doesn't take up any space
+                    TagEnd::HtmlBlock if !wrapper.line_is_blank() => {
                         wrapper.new_line();
+                        wrapper.new_line();
+                    }
+                    TagEnd::FootnoteDefinition => wrapper.end_note(),
+                    TagEnd::List(_) => wrapper.end_list(),
+                    TagEnd::Item => wrapper.end_item(),
+                    TagEnd::Link if wrapper.hyperlinks && wrapper.table.is_none() => {
+                        if let Some(url) = wrapper.link_url.take() {
+                            wrapper.finish_link(url);
+                        }
                     }
                     TagEnd::Link => {
                         wrapper.push_symbol(']');
                         if let Some(url) = wrapper.link_url.take() {
                             wrapper.push_symbol('(');
-                            wrapper.push_text(url);
+                            wrapper.push_text(&url);
                             wrapper.push_symbol(')');
+                            wrapper.finish_link(url);
+                        }
+                    }
+                    TagEnd::MetadataBlock(_) => {
+                        if let Some((kind, source)) = wrapper.metadata_block.take() {
+                            wrapper.metadata = front_matter::parse(&source, kind);
+                        }
+                    }
+                    TagEnd::DefinitionListTitle => wrapper.new_line(),
+                    TagEnd::DefinitionListDefinition => wrapper.end_definition(),
+                    TagEnd::DefinitionList => wrapper.new_line(),
+                    TagEnd::Image => {
+                        if let Some((url, alt)) = wrapper.image.take() {
+                            wrapper.push_image(&url, &alt);
                         }
                     }
                     TagEnd::CodeBlock => {
-                        wrapper.push_text_with_style("```", default_style());
-                        wrapper.new_line();
+                        if let Some((lang, code)) = wrapper.code.take() {
+                            wrapper.push_code_block(&lang, &code);
+                            wrapper.new_line();
+                            wrapper.new_line();
+                        }
+                    }
+                    TagEnd::TableCell => {
+                        let cell = core::mem::take(&mut wrapper.line);
+                        if let Some(row) = wrapper.table.as_mut().and_then(|t| t.rows.last_mut()) {
+                            row.push(cell);
+                        }
+                    }
+                    TagEnd::TableHead => {
+                        if let Some(table) = wrapper.table.as_mut() {
+                            table.head_rows = table.rows.len();
+                        }
+                    }
+                    TagEnd::Table => {
+                        if let Some(table) = wrapper.table.take() {
+                            wrapper.push_table(table);
+                        }
                     }
                     TagEnd::BlockQuote(_) => {
-                        // If there is only 1 span in the line, it is probably the
-                        // prefix, we don't want to render that but just to be defensive
-                        // we check to see if the prefix exists and if the last element
-                        // in the line is the prefix, if not then we render whatever
-                        // is in the line.
                         wrapper.clear_prefix();
                         wrapper.new_line();
                     }
@@ -109,36 +266,54 @@ fn convert(s: &'static str) -> Option<Text<'static>> {
                 }
                 wrapper.clear_style();
             }
+            Event::Text(content) | Event::Code(content) if wrapper.image.is_some() => {
+                if let Some((_, alt)) = wrapper.image.as_mut() {
+                    alt.push_str(&content);
+                }
+            }
             Event::Code(content) => {
                 wrapper.push_symbol('`');
                 wrapper.push_text_with_style(content, default_style());
                 wrapper.push_symbol('`');
             }
-            Event::Text(content)
-            | Event::Html(content)
-            | Event::InlineHtml(content)
-            | Event::FootnoteReference(content) => {
+            Event::Text(content) if wrapper.metadata_block.is_some() => {
+                if let Some((_, source)) = wrapper.metadata_block.as_mut() {
+                    source.push_str(&content);
+                }
+            }
+            Event::Text(content) if wrapper.code.is_some() => {
+                if let Some((_, code)) = wrapper.code.as_mut() {
+                    code.push_str(&content);
+                }
+            }
+            Event::Html(content) | Event::InlineHtml(content) => wrapper.push_html(&content),
+            Event::Text(content) => {
                 wrapper.push_text(content);
             }
+            Event::FootnoteReference(label) => wrapper.footnote_reference(&label),
             Event::SoftBreak => {
-                wrapper.push_symbol(' ');
+                wrapper.push_text(' ');
             }
             Event::HardBreak => {
                 wrapper.new_line();
             }
             Event::Rule => {
-                wrapper.push_text_with_style("-".repeat(5), default_style());
+                let width = wrapper
+                    .width
+                    .map_or(RULE_WIDTH, |width| {
+                        width.saturating_sub(wrapper.prefix_width())
+                    })
+                    .min(RULE_WIDTH);
+                wrapper.push_symbol_with_style("-".repeat(width), default_style());
                 wrapper.new_line();
                 wrapper.new_line();
             }
-            Event::TaskListMarker(complete) => {
-                let text = if complete { "- [x] " } else { "- [ ]" };
-                wrapper.push_text_with_style(text, default_style());
-            }
-            Event::DisplayMath(_) | Event::InlineMath(_) => {},
+            Event::TaskListMarker(complete) => wrapper.task_marker(complete),
+            Event::InlineMath(tex) => wrapper.push_inline_math(&tex),
+            Event::DisplayMath(tex) => wrapper.push_display_math(&tex),
         }
     }
-    Some(wrapper.finish())
+    wrapper.finish()
 }
 
 #[derive(Debug, Default)]
@@ -147,8 +322,168 @@ struct Wrapper {
     lines: Vec<Line<'static>>,
     style: Style,
     line_prefix: Option<Span<'static>>,
-    list_number: Option<u64>,
+    /// The lists the current item is nested in, the innermost last
+    lists: Vec<List>,
+    /// The column continuation lines start at, the content column of the
+    /// current list item
+    indent: usize,
     link_url: Option<String>,
+    /// The line and span index of the start of the current link
+    link_start: Option<(usize, usize)>,
+    links: Vec<Link>,
+    /// Display links as underlined text instead of `[text](url)`
+    hyperlinks: bool,
+    /// The width the text is displayed in, if known
+    width: Option<usize>,
+    /// The colors of code blocks and headings
+    theme: Theme,
+    /// The table being collected, cells are laid out once the whole table
+    /// has been seen
+    table: Option<Table>,
+    /// The language and source of the code block being collected
+    code: Option<(String, String)>,
+    /// The url and alt text of the image being collected
+    image: Option<(String, String)>,
+    images: Vec<Placement>,
+    /// The front matter being collected
+    metadata_block: Option<(MetadataBlockKind, String)>,
+    metadata: Vec<Field>,
+    /// The indent to return to at the end of the current definition
+    definition: Option<usize>,
+    /// The heading being collected
+    heading: Option<Heading>,
+    headings: Vec<Heading>,
+    /// The label of each footnote in the order they are numbered
+    footnotes: Vec<String>,
+    /// The link index and footnote number of each footnote reference
+    references: Vec<(usize, usize)>,
+    /// The number of the footnote definition being collected and the text
+    /// around it
+    outer: Option<(usize, Outer)>,
+    /// The footnote definitions, displayed at the end
+    notes: Vec<Note>,
+    /// Splits inline and block HTML into tags and text
+    html: html::Tokenizer,
+    /// The tag whose contents are dropped, e.g. `script`
+    skip_tag: Option<String>,
+    /// The indexes of the `<details>` sections that are displayed the
+    /// opposite of their `open` attribute
+    toggled: Vec<usize>,
+    /// The number of `<details>` sections seen so far
+    details_count: usize,
+    /// The `<details>` sections the current text is in, the innermost last
+    details: Vec<Details>,
+    /// The depth of the collapsed `<details>` whose contents are being
+    /// dropped and the text around it
+    hidden: Option<(usize, Outer)>,
+}
+
+#[derive(Debug)]
+struct Details {
+    idx: usize,
+    expanded: bool,
+}
+
+/// The state set aside while something is collected on its own, a
+/// footnote definition or the contents of a collapsed `<details>`
+#[derive(Debug)]
+struct Outer {
+    lines: Vec<Line<'static>>,
+    line: Vec<Span<'static>>,
+    indent: usize,
+    links: Vec<Link>,
+    headings: Vec<Heading>,
+}
+
+/// A footnote definition, the positions of its links and headings are
+/// relative to its first line
+#[derive(Debug)]
+struct Note {
+    number: usize,
+    lines: Vec<Line<'static>>,
+    links: Vec<Link>,
+    headings: Vec<Heading>,
+}
+
+const SUPERSCRIPT: [char; 10] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+
+fn superscript(number: usize) -> String {
+    number
+        .to_string()
+        .chars()
+        .filter_map(|c| c.to_digit(10))
+        .map(|d| SUPERSCRIPT[d as usize])
+        .collect()
+}
+
+#[derive(Debug)]
+struct List {
+    /// The number of the next item in an ordered list
+    number: Option<u64>,
+    /// The column the current item's bullet or number is displayed at
+    marker: usize,
+    /// The column the current item's text starts at
+    content: usize,
+}
+
+const BULLETS: [&str; 3] = ["•", "◦", "▪"];
+
+/// How far a definition is indented from its term
+const DEFINITION_INDENT: usize = 4;
+
+/// How far display math is indented when the width isn't known to center it
+const DISPLAY_MATH_INDENT: usize = 4;
+
+/// The widest a horizontal rule is drawn, narrower views draw it to the edge
+const RULE_WIDTH: usize = 40;
+
+#[derive(Debug, Default)]
+struct Table {
+    alignments: Vec<Alignment>,
+    /// The contents of the line before the table started, e.g. a block
+    /// quote's prefix
+    prefix: Vec<Span<'static>>,
+    /// The spans of each cell in each row
+    rows: Vec<Vec<Vec<Span<'static>>>>,
+    /// The number of rows in the table's header
+    head_rows: usize,
+}
+
+impl Table {
+    /// The width of each column, when the table is wider than `width` the
+    /// widest columns are shrunk first. `None` when the borders and a single
+    /// column of content for each column don't fit in `width`.
+    fn column_widths(&self, width: Option<usize>) -> Option<Vec<usize>> {
+        let columns = self.rows.iter().map(Vec::len).max().unwrap_or_default();
+        let mut natural = vec![1; columns];
+        for row in &self.rows {
+            for (idx, cell) in row.iter().enumerate() {
+                natural[idx] = natural[idx].max(spans_width(cell));
+            }
+        }
+        // each column has a border and a space on either side of its content
+        let border = columns * 3 + 1;
+        let Some(width) = width else {
+            return Some(natural);
+        };
+        if width < border + columns {
+            return None;
+        }
+        let available = width - border;
+        if natural.iter().sum::<usize>() <= available {
+            return Some(natural);
+        }
+        let mut order: Vec<usize> = (0..columns).collect();
+        order.sort_by_key(|idx| natural[*idx]);
+        let mut remaining = available;
+        let mut ret = natural.clone();
+        for (done, idx) in order.into_iter().enumerate() {
+            let share = remaining / (columns - done);
+            ret[idx] = natural[idx].min(share).max(1);
+            remaining = remaining.saturating_sub(ret[idx]);
+        }
+        Some(ret)
+    }
 }
 
 impl Wrapper {
@@ -165,16 +500,82 @@ impl Wrapper {
         self.line.push(ch.to_string().into());
     }
 
+    /// Push a styled symbol into the current line without wrapping, for
+    /// borders and other text that is already laid out
+    fn push_symbol_with_style(&mut self, content: impl ToString, style: Style) {
+        self.line.push(Span::styled(content.to_string(), style));
+    }
+
     /// Push text into the current line, wrapping as needed
     fn push_text(&mut self, content: impl ToString) {
+        let mut content = content.to_string();
+        if let Some(heading) = self.heading.as_mut() {
+            if heading.level >= 5 {
+                content = content.to_uppercase();
+            }
+            heading.text.push_str(&content);
+        }
         self.push_text_with_style(content, self.style);
     }
 
     /// Push text into the current line, wrapping as needed
     fn push_text_with_style(&mut self, content: impl ToString, style: Style) {
         let content = content.to_string();
-        let span = Span::from(content).style(style);
-        self.line.push(span);
+        // table cells are wrapped to their column when the table is drawn
+        let Some(width) = self.width.filter(|_| self.table.is_none()) else {
+            self.line.push(Span::styled(content, style));
+            return;
+        };
+        let words = content
+            .split_inclusive(char::is_whitespace)
+            .flat_map(|word| {
+                let trimmed = word.trim_end();
+                [trimmed, &word[trimmed.len()..]]
+            })
+            .filter(|word| !word.is_empty());
+        for word in words {
+            let current = spans_width(&self.line);
+            let start = self.prefix_width();
+            let word_width = word.width();
+            if word.trim().is_empty() {
+                // whitespace isn't carried onto the next line or used to
+                // start a line
+                if current > start && current + word_width <= width {
+                    self.line.push(Span::styled(word.to_string(), style));
+                }
+                continue;
+            }
+            if current > start && current + word_width > width {
+                self.wrap_line();
+            }
+            if word_width <= width.saturating_sub(start) {
+                self.line.push(Span::styled(word.to_string(), style));
+                continue;
+            }
+            // a word too long for a line of its own is split wherever it
+            // reaches the edge
+            for ch in word.chars() {
+                let current = spans_width(&self.line);
+                if current > self.prefix_width() && current + ch.to_string().width() > width {
+                    self.wrap_line();
+                }
+                self.line.push(Span::styled(ch.to_string(), style));
+            }
+        }
+    }
+
+    /// Start a new line because the current one is full, the continuation
+    /// starts with the same prefix and indentation
+    fn wrap_line(&mut self) {
+        while self
+            .line
+            .last()
+            .is_some_and(|span| !span.content.is_empty() && span.content.trim().is_empty())
+            && spans_width(&self.line) > self.prefix_width()
+        {
+            self.line.pop();
+        }
+        self.new_line();
     }
 
     fn new_line(&mut self) {
@@ -184,6 +585,290 @@ impl Wrapper {
             // self.current_width += prefix.width();
             self.line.push(prefix);
         }
+        if self.indent > 0 {
+            self.line.push(" ".repeat(self.indent).into());
+        }
+    }
+
+    /// Headings get smaller as the level increases, the first two levels
+    /// are underlined with a rule and the last two are uppercase
+    fn start_heading(&mut self, level: u8) {
+        let accent = self.theme.heading;
+        self.style = match level {
+            1 | 2 | 5 => default_style().add_modifier(Modifier::BOLD),
+            3 => default_style().fg(accent).add_modifier(Modifier::BOLD),
+            4 => default_style().fg(accent),
+            _ => default_style().add_modifier(Modifier::ITALIC),
+        };
+        self.heading = Some(Heading {
+            level,
+            text: String::new(),
+            line: self.lines.len(),
+        });
+    }
+
+    fn end_heading(&mut self) {
+        let Some(heading) = self.heading.take() else {
+            return;
+        };
+        let rule = match heading.level {
+            1 => Some("━"),
+            2 => Some("─"),
+            _ => None,
+        };
+        let width = heading.text.width();
+        self.headings.push(heading);
+        self.new_line();
+        if let Some(rule) = rule {
+            let max = self
+                .width
+                .map(|w| w.saturating_sub(self.prefix_width()))
+                .unwrap_or(usize::MAX);
+            self.push_symbol_with_style(rule.repeat(width.min(max)), default_style());
+            self.new_line();
+        }
+    }
+
+    /// If the current line only contains the line prefix or indentation
+    fn line_is_blank(&self) -> bool {
+        self.line
+            .iter()
+            .all(|span| Some(span) == self.line_prefix.as_ref() || span.content.trim().is_empty())
+    }
+
+    fn start_list(&mut self, number: Option<u64>) {
+        // a nested list starts on the line after its parent item's text
+        if !self.line_is_blank() {
+            self.new_line();
+        }
+        let column = self.lists.last().map(|l| l.content).unwrap_or_default();
+        self.lists.push(List {
+            number,
+            marker: column,
+            content: column,
+        });
+    }
+
+    fn end_list(&mut self) {
+        self.lists.pop();
+        self.indent = self.lists.last().map(|l| l.content).unwrap_or_default();
+        // only the outermost list is followed by a blank line
+        if self.lists.is_empty() {
+            self.new_line();
+        }
+    }
+
+    /// Push the bullet or number for a new item, any lines after the first
+    /// are indented to line up with the item's text
+    fn start_item(&mut self) {
+        if !self.line_is_blank() {
+            self.new_line();
+        }
+        let depth = self.lists.len().saturating_sub(1);
+        let Some(list) = self.lists.last_mut() else {
+            return;
+        };
+        let marker = match list.number.as_mut() {
+            Some(number) => {
+                *number += 1;
+                format!("{}.", *number - 1)
+            }
+            None => BULLETS[depth % BULLETS.len()].to_string(),
+        };
+        list.content = list.marker + marker.width() + 1;
+        let (column, content) = (list.marker, list.content);
+        self.line.clear();
+        if let Some(prefix) = self.line_prefix.clone() {
+            self.line.push(prefix);
+        }
+        if column > 0 {
+            self.push_symbol(" ".repeat(column));
+        }
+        self.push_symbol_with_style(format!("{marker} "), default_style());
+        self.indent = content;
+    }
+
+    fn end_item(&mut self) {
+        // the next line belongs to the parent item, if any
+        self.indent = match self.lists.len() {
+            0 | 1 => 0,
+            len => self.lists[len - 2].content,
+        };
+        if !self.line_is_blank() {
+            self.new_line();
+        }
+    }
+
+    /// A definition is indented under its term
+    fn start_definition(&mut self) {
+        if !self.line_is_blank() {
+            self.new_line();
+        }
+        self.definition = Some(self.indent);
+        self.indent += DEFINITION_INDENT;
+        self.line.clear();
+        if let Some(prefix) = self.line_prefix.clone() {
+            self.line.push(prefix);
+        }
+        self.push_symbol(" ".repeat(self.indent));
+    }
+
+    fn end_definition(&mut self) {
+        self.indent = self.definition.take().unwrap_or_default();
+        if !self.line_is_blank() {
+            self.new_line();
+        }
+        // the next term isn't indented
+        self.line.clear();
+        if let Some(prefix) = self.line_prefix.clone() {
+            self.line.push(prefix);
+        }
+        if self.indent > 0 {
+            self.push_symbol(" ".repeat(self.indent));
+        }
+    }
+
+    /// Replace the current item's bullet with a checkbox
+    fn task_marker(&mut self, complete: bool) {
+        let Some(list) = self.lists.last_mut() else {
+            return;
+        };
+        let checkbox = if complete { "[x] " } else { "[ ] " };
+        if list.number.is_none() {
+            self.line.pop();
+            list.content = list.marker + checkbox.width();
+        } else {
+            list.content += checkbox.width();
+        }
+        self.indent = list.content;
+        self.push_symbol_with_style(checkbox, default_style());
+    }
+
+    /// Lay out a table with box drawing borders, wrapping the cells of any
+    /// columns that were shrunk to fit the width
+    fn push_table(&mut self, table: Table) {
+        let width = self.width.map(|w| w.saturating_sub(self.prefix_width()));
+        let Some(widths) = table.column_widths(width) else {
+            self.push_stacked_table(table, width.unwrap_or_default());
+            return;
+        };
+        let border = |left: &str, mid: &str, right: &str| {
+            let segments: Vec<String> = widths.iter().map(|w| "─".repeat(w + 2)).collect();
+            Span::styled(
+                format!("{left}{}{right}", segments.join(mid)),
+                default_style(),
+            )
+        };
+        self.line.extend(table.prefix.iter().cloned());
+        self.line.push(border("┌", "┬", "┐"));
+        self.new_line();
+        for (row_idx, row) in table.rows.iter().enumerate() {
+            if row_idx > 0 && row_idx == table.head_rows {
+                self.line.push(border("├", "┼", "┤"));
+                self.new_line();
+            }
+            let cells: Vec<Vec<Vec<Span<'static>>>> = widths
+                .iter()
+                .enumerate()
+                .map(|(idx, width)| {
+                    row.get(idx)
+                        .map(|cell| wrap_spans(cell, *width))
+                        .unwrap_or_default()
+                })
+                .collect();
+            let height = cells.iter().map(Vec::len).max().unwrap_or_default().max(1);
+            for line_idx in 0..height {
+                for (col, (cell, width)) in cells.iter().zip(&widths).enumerate() {
+                    let content = cell.get(line_idx).cloned().unwrap_or_default();
+                    let padding = width.saturating_sub(spans_width(&content));
+                    let (before, after) = match table.alignments.get(col) {
+                        Some(Alignment::Center) => (padding / 2, padding - padding / 2),
+                        Some(Alignment::Right) => (padding, 0),
+                        _ => (0, padding),
+                    };
+                    self.push_symbol_with_style("│", default_style());
+                    self.push_symbol(" ".repeat(before + 1));
+                    self.line.extend(content);
+                    self.push_symbol(" ".repeat(after + 1));
+                }
+                self.push_symbol_with_style("│", default_style());
+                self.new_line();
+            }
+        }
+        self.line.push(border("└", "┴", "┘"));
+        self.new_line();
+        self.new_line();
+    }
+
+    /// Lay out a table too narrow for its borders as its rows one after
+    /// another, each cell on its own lines after its column's heading
+    fn push_stacked_table(&mut self, table: Table, width: usize) {
+        let width = width.max(1);
+        let head_rows = table.head_rows.min(table.rows.len());
+        let (head, body) = match table.rows.split_at(head_rows) {
+            (head, []) => (&[][..], head),
+            split => split,
+        };
+        self.line.extend(table.prefix.iter().cloned());
+        for row in body {
+            for (idx, cell) in row.iter().enumerate() {
+                let mut spans = Vec::new();
+                if let Some(heading) = head.first().and_then(|h| h.get(idx)) {
+                    spans.extend(heading.iter().cloned());
+                    spans.push(Span::styled(": ", default_style()));
+                }
+                spans.extend(cell.iter().cloned());
+                for line in wrap_spans(&spans, width) {
+                    self.line.extend(line);
+                    self.new_line();
+                }
+            }
+            self.new_line();
+        }
+    }
+
+    /// Push a syntax highlighted code block in a shaded box with the language
+    /// in the top border, lines wider than the box are wrapped
+    fn push_code_block(&mut self, lang: &str, code: &str) {
+        let lines = highlight(lang, code, &self.theme);
+        let border_style = default_style();
+        let background = Style::new().bg(self.theme.code_background);
+        let longest = lines.iter().map(Line::width).max().unwrap_or_default();
+        let label = lang.trim();
+        // a border and a space of padding on either side
+        let inner = match self.width {
+            Some(width) => width.saturating_sub(4 + self.prefix_width()).max(1),
+            None => longest.max(label.width() + 2),
+        };
+        let top = if label.is_empty() {
+            "─".repeat(inner + 2)
+        } else {
+            let label = format!(" {label} ");
+            let rest = (inner + 2).saturating_sub(label.width() + 1);
+            format!("─{label}{}", "─".repeat(rest))
+        };
+        self.push_symbol_with_style(format!("┌{top}┐"), border_style);
+        self.new_line();
+        for line in lines {
+            for chunk in wrap_code(&line.spans, inner) {
+                let padding = inner.saturating_sub(spans_width(&chunk));
+                self.push_symbol_with_style("│", border_style);
+                self.push_symbol_with_style(" ", background);
+                self.line
+                    .extend(chunk.into_iter().map(|span| span.patch_style(background)));
+                self.push_symbol_with_style(" ".repeat(padding + 1), background);
+                self.push_symbol_with_style("│", border_style);
+                self.new_line();
+            }
+        }
+        self.push_symbol_with_style(format!("└{}┘", "─".repeat(inner + 2)), border_style);
+    }
+
+    /// The width of the line prefix and indentation pushed at the start of
+    /// every line
+    fn prefix_width(&self) -> usize {
+        let prefix = self.line_prefix.as_ref().map(Span::width);
+        prefix.unwrap_or_default() + self.indent
     }
 
     fn clear_style(&mut self) {
@@ -198,12 +883,417 @@ impl Wrapper {
         self.style = self.style.add_modifier(modif);
     }
 
-    fn finish(mut self) -> Text<'static> {
+    fn remove_style(&mut self, modif: Modifier) {
+        self.style = self.style.remove_modifier(modif);
+    }
+
+    /// Record the position of the link that was just pushed
+    fn finish_link(&mut self, url: String) {
+        let Some(start) = self.link_start.take() else {
+            return;
+        };
+        self.links.push(Link {
+            url,
+            spans: self.spans_since(start),
+            jump: None,
+            toggle: None,
+        });
+    }
+
+    /// The spans on each line from the line and span index `start` through
+    /// the end of the current line, the prefix and indentation of a
+    /// continuation line are skipped
+    fn spans_since(&self, (line, start): (usize, usize)) -> Vec<(usize, Range<usize>)> {
+        let prefix = usize::from(self.line_prefix.is_some()) + usize::from(self.indent > 0);
+        let mut ret: Vec<_> = (line..=self.lines.len())
+            .filter_map(|idx| {
+                let len = self
+                    .lines
+                    .get(idx)
+                    .map_or(self.line.len(), |line| line.spans.len());
+                let first = if idx == line { start } else { prefix.min(len) };
+                (first < len).then_some((idx, first..len))
+            })
+            .collect();
+        if ret.is_empty() {
+            ret.push((self.lines.len(), self.line.len()..self.line.len()));
+        }
+        ret
+    }
+
+    /// Display the safe subset of HTML, line breaks, text styles, `<kbd>`
+    /// and `<details>`, dropping comments, scripts and any other tags
+    fn push_html(&mut self, content: &str) {
+        for token in self.html.tokens(content) {
+            if let Some(tag) = self.skip_tag.as_ref() {
+                if token == Token::Close(tag.clone()) {
+                    self.skip_tag = None;
+                }
+                continue;
+            }
+            match token {
+                Token::Text(text) => {
+                    let text = text.replace('\n', " ");
+                    let text = if self.line_is_blank() {
+                        text.trim_start()
+                    } else {
+                        &text
+                    };
+                    if !text.is_empty() {
+                        self.push_text(text);
+                    }
+                }
+                Token::Open { name, attrs } => match name.as_str() {
+                    "br" => self.new_line(),
+                    "b" | "strong" => self.modify_style(Modifier::BOLD),
+                    "i" | "em" => self.modify_style(Modifier::ITALIC),
+                    "u" | "ins" => self.modify_style(Modifier::UNDERLINED),
+                    "s" | "del" | "strike" => self.modify_style(Modifier::CROSSED_OUT),
+                    "kbd" => {
+                        self.push_symbol_with_style('[', self.style);
+                        self.modify_style(Modifier::BOLD);
+                    }
+                    "details" => self.start_details(html::has_attr(&attrs, "open")),
+                    "summary" => self.start_summary(),
+                    "script" | "style" => self.skip_tag = Some(name),
+                    _ => {}
+                },
+                Token::Close(name) => match name.as_str() {
+                    "b" | "strong" => self.remove_style(Modifier::BOLD),
+                    "i" | "em" => self.remove_style(Modifier::ITALIC),
+                    "u" | "ins" => self.remove_style(Modifier::UNDERLINED),
+                    "s" | "del" | "strike" => self.remove_style(Modifier::CROSSED_OUT),
+                    "kbd" => {
+                        self.remove_style(Modifier::BOLD);
+                        self.push_symbol_with_style(']', self.style);
+                    }
+                    "summary" => self.end_summary(),
+                    "details" => self.end_details(),
+                    _ => {}
+                },
+            }
+        }
+    }
+
+    fn start_details(&mut self, open: bool) {
+        if !self.line_is_blank() {
+            self.new_line();
+        }
+        let idx = self.details_count;
+        self.details_count += 1;
+        self.details.push(Details {
+            idx,
+            expanded: open != self.toggled.contains(&idx),
+        });
+    }
+
+    /// The summary starts with an arrow showing if the section is expanded
+    /// and can be focused to expand or collapse it
+    fn start_summary(&mut self) {
+        let Some(expanded) = self.details.last().map(|d| d.expanded) else {
+            return;
+        };
+        if !self.line_is_blank() {
+            self.new_line();
+        }
+        let marker = if expanded { "▼ " } else { "▶ " };
+        self.link_start = Some((self.lines.len(), self.line.len()));
+        self.push_symbol_with_style(marker, default_style());
+        self.modify_style(Modifier::BOLD);
+    }
+
+    /// A collapsed section's contents are collected on their own and
+    /// dropped at the end of the section
+    fn end_summary(&mut self) {
+        self.remove_style(Modifier::BOLD);
+        let Some(details) = self.details.last() else {
+            return;
+        };
+        let (idx, expanded) = (details.idx, details.expanded);
+        if let Some(start) = self.link_start.take() {
+            self.links.push(Link {
+                url: String::new(),
+                spans: self.spans_since(start),
+                jump: None,
+                toggle: Some(idx),
+            });
+        }
+        if !expanded && self.hidden.is_none() {
+            let outer = self.set_aside();
+            self.hidden = Some((self.details.len(), outer));
+        }
+    }
+
+    fn end_details(&mut self) {
+        let depth = self.details.len();
+        self.details.pop();
+        if self
+            .hidden
+            .as_ref()
+            .is_some_and(|(hidden, _)| *hidden == depth)
+        {
+            if let Some((_, outer)) = self.hidden.take() {
+                self.restore(outer);
+            }
+        }
+        // a collapsed section leaves off after its summary
+        if !self.line_is_blank() {
+            self.new_line();
+            self.new_line();
+        }
+    }
+
+    /// Display an image as character art when art was generated for it at
+    /// build time, otherwise as a placeholder with its alt text
+    fn push_image(&mut self, url: &str, alt: &str) {
+        let label = if alt.trim().is_empty() {
+            url.rsplit('/').next().unwrap_or(url)
+        } else {
+            alt
+        };
+        let art = Image::find(url).filter(|_| self.table.is_none());
+        let Some(image) = art else {
+            let style = self.style.add_modifier(Modifier::ITALIC);
+            self.push_text_with_style(format!("[image: {label}]"), style);
+            return;
+        };
+        if !self.line_is_blank() {
+            self.new_line();
+        }
+        let width = self
+            .width
+            .map(|w| w.saturating_sub(self.prefix_width()))
+            .unwrap_or(usize::MAX);
+        let lines = image_lines(image, width);
+        // art in a footnote or collapsed section isn't where it was drawn
+        if self.outer.is_none() && self.hidden.is_none() {
+            self.images.push(Placement {
+                image,
+                line: self.lines.len(),
+                column: spans_width(&self.line),
+                columns: lines.first().map(Line::width).unwrap_or_default(),
+                rows: lines.len(),
+            });
+        }
+        for line in lines {
+            self.line.extend(line.spans);
+            self.new_line();
+        }
+        let style = self.style.add_modifier(Modifier::ITALIC);
+        self.push_text_with_style(label, style);
+    }
+
+    /// Math that can't be approximated with unicode is displayed as its TeX
+    /// source like inline code
+    fn push_inline_math(&mut self, tex: &str) {
+        if let Some(text) = math::inline(tex) {
+            self.push_text_with_style(text, self.style);
+            return;
+        }
+        self.push_symbol('`');
+        self.push_text_with_style(tex, default_style());
+        self.push_symbol('`');
+    }
+
+    /// Display math is centered on its own lines, math that can't be
+    /// approximated or doesn't fit is displayed as a TeX code block
+    fn push_display_math(&mut self, tex: &str) {
+        let available = self
+            .width
+            .map(|w| w.saturating_sub(self.prefix_width()))
+            .unwrap_or(usize::MAX);
+        let lines = math::display(tex)
+            .filter(|lines| lines.iter().all(|line| line.width() <= available))
+            .filter(|_| self.table.is_none());
+        let Some(lines) = lines else {
+            if !self.line_is_blank() {
+                self.new_line();
+            }
+            self.push_code_block("tex", tex.trim());
+            return;
+        };
+        if !self.line_is_blank() {
+            self.new_line();
+        }
+        let width = lines
+            .iter()
+            .map(|line| line.width())
+            .max()
+            .unwrap_or_default();
+        let pad = self
+            .width
+            .map(|_| (available - width) / 2)
+            .unwrap_or(DISPLAY_MATH_INDENT);
+        for (idx, line) in lines.iter().enumerate() {
+            if idx > 0 {
+                self.new_line();
+            }
+            self.push_symbol_with_style(format!("{}{line}", " ".repeat(pad)), self.style);
+        }
+    }
+
+    /// Footnotes are numbered in the order their labels first appear
+    fn footnote_number(&mut self, label: &str) -> usize {
+        let idx = match self.footnotes.iter().position(|l| l == label) {
+            Some(idx) => idx,
+            None => {
+                self.footnotes.push(label.to_string());
+                self.footnotes.len() - 1
+            }
+        };
+        idx + 1
+    }
+
+    /// Push a superscript marker for a footnote, outside of tables and other
+    /// notes the marker can be focused to jump to the note
+    fn footnote_reference(&mut self, label: &str) {
+        let number = self.footnote_number(label);
+        let marker = superscript(number);
+        if let Some(width) = self.width.filter(|_| self.table.is_none()) {
+            if spans_width(&self.line) + marker.width() > width {
+                self.wrap_line();
+            }
+        }
+        self.push_symbol_with_style(marker, default_style().fg(self.theme.heading));
+        if self.outer.is_none() && self.hidden.is_none() && self.table.is_none() {
+            self.references.push((self.links.len(), number));
+            self.links.push(Link {
+                url: String::new(),
+                spans: vec![(self.lines.len(), self.line.len() - 1..self.line.len())],
+                jump: None,
+                toggle: None,
+            });
+        }
+    }
+
+    /// Set the text aside to collect a footnote definition, the note starts
+    /// with its number and wraps under its text
+    fn start_note(&mut self, label: &str) {
+        let number = self.footnote_number(label);
+        let outer = self.set_aside();
+        self.outer = Some((number, outer));
+        let marker = format!("{number}. ");
+        self.indent = marker.width();
+        self.push_symbol_with_style(marker, default_style().add_modifier(Modifier::BOLD));
+    }
+
+    fn end_note(&mut self) {
+        let Some((number, outer)) = self.outer.take() else {
+            return;
+        };
+        if !self.line_is_blank() {
+            self.new_line();
+        }
+        let (mut lines, links, headings) = self.restore(outer);
+        while lines
+            .last()
+            .is_some_and(|line| line.spans.iter().all(|s| s.content.trim().is_empty()))
+        {
+            lines.pop();
+        }
+        self.notes.push(Note {
+            number,
+            lines,
+            links,
+            headings,
+        });
+    }
+
+    /// Set the text collected so far aside so what follows can be collected
+    /// on its own
+    fn set_aside(&mut self) -> Outer {
+        Outer {
+            lines: core::mem::take(&mut self.lines),
+            line: core::mem::take(&mut self.line),
+            indent: self.indent,
+            links: core::mem::take(&mut self.links),
+            headings: core::mem::take(&mut self.headings),
+        }
+    }
+
+    /// Put the text that was set aside back, returning the lines, links and
+    /// headings collected since
+    fn restore(&mut self, outer: Outer) -> (Vec<Line<'static>>, Vec<Link>, Vec<Heading>) {
+        self.line = outer.line;
+        self.indent = outer.indent;
+        (
+            core::mem::replace(&mut self.lines, outer.lines),
+            core::mem::replace(&mut self.links, outer.links),
+            core::mem::replace(&mut self.headings, outer.headings),
+        )
+    }
+
+    /// Display the footnote definitions in order under a heading at the end,
+    /// each reference jumps to its note's number and the number jumps back
+    /// to the first reference
+    fn push_notes(&mut self) {
+        let mut notes = core::mem::take(&mut self.notes);
+        if notes.is_empty() {
+            return;
+        }
+        notes.sort_by_key(|note| note.number);
+        if self.lines.last().is_some_and(|line| line.width() > 0) {
+            self.new_line();
+        }
+        self.start_heading(2);
+        self.push_text("Notes");
+        self.end_heading();
+        self.clear_style();
+        let mut markers = Vec::new();
+        for note in notes {
+            let base = self.lines.len();
+            let first_reference = self
+                .references
+                .iter()
+                .find(|(_, number)| *number == note.number)
+                .map(|(idx, _)| *idx);
+            if let Some(reference) = first_reference {
+                markers.push((note.number, self.links.len()));
+                self.links.push(Link {
+                    url: String::new(),
+                    spans: vec![(base, 0..1)],
+                    jump: Some(reference),
+                    toggle: None,
+                });
+            }
+            self.links.extend(note.links.into_iter().map(|link| {
+                Link {
+                    spans: link
+                        .spans
+                        .into_iter()
+                        .map(|(line, spans)| (line + base, spans))
+                        .collect(),
+                    ..link
+                }
+            }));
+            self.headings
+                .extend(note.headings.into_iter().map(|heading| Heading {
+                    line: heading.line + base,
+                    ..heading
+                }));
+            self.lines.extend(note.lines);
+        }
+        for (idx, number) in &self.references {
+            self.links[*idx].jump = markers
+                .iter()
+                .find(|(n, _)| n == number)
+                .map(|(_, marker)| *marker);
+        }
+    }
+
+    fn finish(mut self) -> Document {
         if !self.line.is_empty() {
             self.new_line()
         }
+        self.push_notes();
 
-        Text::from(self.lines).left_aligned()
+        Document {
+            text: Text::from(self.lines).left_aligned(),
+            links: self.links,
+            headings: self.headings,
+            metadata: self.metadata,
+            images: self.images,
+        }
     }
 
     /// Clear the line prefix and any potentially empty lines with that prefix
@@ -236,6 +1326,138 @@ impl Wrapper {
     }
 }
 
+fn spans_width(spans: &[Span]) -> usize {
+    spans.iter().map(Span::width).sum()
+}
+
+/// Draw an image with two pixels per character cell using half blocks,
+/// shrinking it to fit in `width` columns
+fn image_lines(image: &Image, width: usize) -> Vec<Line<'static>> {
+    let columns = image.width.min(width).max(1);
+    let rows = (image.height() * columns).div_ceil(image.width.max(1));
+    let pixel = |x: usize, y: usize| {
+        let x = x * image.width / columns;
+        let y = y * image.height() / rows.max(1);
+        image
+            .pixels
+            .get(y * image.width + x)
+            .copied()
+            .flatten()
+            .map(|[r, g, b]| Color::Rgb(r, g, b))
+    };
+    (0..rows)
+        .step_by(2)
+        .map(|y| {
+            let spans: Vec<Span<'static>> = (0..columns)
+                .map(|x| {
+                    let below = if y + 1 < rows { pixel(x, y + 1) } else { None };
+                    match (pixel(x, y), below) {
+                        (Some(top), Some(bottom)) => {
+                            Span::styled("▀", Style::new().fg(top).bg(bottom))
+                        }
+                        (Some(top), None) => Span::styled("▀", Style::new().fg(top)),
+                        (None, Some(bottom)) => Span::styled("▄", Style::new().fg(bottom)),
+                        (None, None) => Span::raw(" "),
+                    }
+                })
+                .collect();
+            Line::from(spans)
+        })
+        .collect()
+}
+
+/// Break `spans` into lines no wider than `width`, breaking between words
+/// where possible and splitting any word wider than `width`
+fn wrap_spans(spans: &[Span<'static>], width: usize) -> Vec<Vec<Span<'static>>> {
+    let mut lines = vec![Vec::new()];
+    let mut current = 0;
+    for span in spans {
+        let words = span
+            .content
+            .split_inclusive(char::is_whitespace)
+            .flat_map(|word| {
+                let trimmed = word.trim_end();
+                let space = &word[trimmed.len()..];
+                [trimmed, space]
+            })
+            .filter(|word| !word.is_empty());
+        for word in words {
+            let is_space = word.trim().is_empty();
+            if is_space {
+                // spaces that would start or overflow a line are dropped
+                if current > 0 && current < width {
+                    lines
+                        .last_mut()
+                        .unwrap()
+                        .push(Span::styled(" ", span.style));
+                    current += 1;
+                }
+                continue;
+            }
+            let word_width = word.width();
+            if current > 0 && current + word_width > width {
+                trim_end(lines.last_mut().unwrap());
+                lines.push(Vec::new());
+                current = 0;
+            }
+            if word_width <= width {
+                lines
+                    .last_mut()
+                    .unwrap()
+                    .push(Span::styled(word.to_string(), span.style));
+                current += word_width;
+                continue;
+            }
+            for ch in word.chars() {
+                let ch_width = ch.to_string().width();
+                if current > 0 && current + ch_width > width {
+                    lines.push(Vec::new());
+                    current = 0;
+                }
+                lines
+                    .last_mut()
+                    .unwrap()
+                    .push(Span::styled(ch.to_string(), span.style));
+                current += ch_width;
+            }
+        }
+    }
+    if let Some(last) = lines.last_mut() {
+        trim_end(last);
+    }
+    lines
+}
+
+/// Break `spans` into lines no wider than `width` without regard for words,
+/// unlike [`wrap_spans`] whitespace is kept so indentation is preserved
+fn wrap_code(spans: &[Span<'static>], width: usize) -> Vec<Vec<Span<'static>>> {
+    let mut lines: Vec<Vec<Span<'static>>> = vec![Vec::new()];
+    let mut current = 0;
+    for span in spans {
+        for ch in span.content.chars() {
+            let ch_width = ch.to_string().width();
+            if current > 0 && current + ch_width > width {
+                lines.push(Vec::new());
+                current = 0;
+            }
+            let line = lines.last_mut().unwrap();
+            match line.last_mut() {
+                Some(last) if last.style == span.style => last.content.to_mut().push(ch),
+                _ => line.push(Span::styled(ch.to_string(), span.style)),
+            }
+            current += ch_width;
+        }
+    }
+    lines
+}
+
+/// Remove a trailing space left when a line was broken after a word
+fn trim_end(line: &mut Vec<Span<'static>>) {
+    if line.last().is_some_and(|span| span.content == " ") {
+        line.pop();
+    }
+}
+
 #[cfg(test)]
 mod test {
     use ratatui::{
@@ -283,9 +1505,328 @@ fn main() {
 > don't forget that
 > these also auto-wrap
         "#;
+        assert_rendered!(convert_document(md, false, Some(45)).text);
+    }
+
+    #[test]
+    fn convert_markdown_wrap() {
+        let md = r#"a paragraph that is long enough to need more than one line at this width
+
+- a list item that wraps onto a second line under its text
+  1. and a nested item that also wraps under its own text
+
+> a block quote that keeps its bar on every line it wraps onto
+"#;
+        let text = convert_document(md, false, Some(30)).text;
+        assert!(text.lines.iter().all(|line| line.width() <= 30));
+        assert_rendered!(text);
+    }
+
+    #[test]
+    fn convert_markdown_links() {
+        let md = "see [one](https://a.example)\n\nand [two](https://b.example)";
+        let Document { text, links, .. } = convert_document(md, false, None);
+        assert_eq!(
+            links,
+            vec![
+                Link {
+                    url: "https://a.example".to_string(),
+                    spans: vec![(0, 1..7)],
+                    jump: None,
+                    toggle: None,
+                },
+                Link {
+                    url: "https://b.example".to_string(),
+                    spans: vec![(2, 1..7)],
+                    jump: None,
+                    toggle: None,
+                },
+            ]
+        );
+        let rendered: String = text.lines[2].spans[links[1].spans[0].1.clone()]
+            .iter()
+            .map(|s| s.content.as_ref())
+            .collect();
+        assert_eq!(rendered, "[two](https://b.example)");
+
+        let Document { text, links, .. } = convert_document(md, true, None);
+        assert_eq!(links[1].spans, vec![(2, 1..2)]);
+        let span = &text.lines[2].spans[1];
+        assert_eq!(span.content, "two");
+        assert!(span.style.add_modifier.contains(Modifier::UNDERLINED));
+    }
+
+    #[test]
+    fn convert_markdown_wrapped_link() {
+        let md = "> see [a link that wraps](https://a.example) here";
+        let Document { text, links, .. } = convert_document(md, true, Some(20));
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].lines(), 0..2);
+        let rendered: Vec<String> = links[0]
+            .spans
+            .iter()
+            .map(|(line, spans)| {
+                text.lines[*line].spans[spans.clone()]
+                    .iter()
+                    .map(|s| s.content.as_ref())
+                    .collect()
+            })
+            .collect();
+        assert_eq!(rendered, vec!["a link that", "wraps"]);
+    }
+
+    #[test]
+    fn convert_markdown_footnotes() {
+        let md = r#"A claim[^source] and another[^2] and the first again[^source].
+
+[^2]: A second note that is long enough to wrap under its number.
+[^source]: See [the source](https://example.com).
+"#;
+        let Document {
+            text,
+            links,
+            headings,
+            ..
+        } = convert_document(md, true, Some(40));
+        let jumps: Vec<_> = links.iter().map(|l| (l.lines().start, l.jump)).collect();
+        assert_eq!(
+            jumps,
+            vec![
+                (0, Some(3)),
+                (0, Some(5)),
+                (1, Some(3)),
+                (5, Some(0)),
+                (5, None),
+                (6, Some(1)),
+            ]
+        );
+        assert_eq!(headings.last().map(|h| h.text.as_str()), Some("Notes"));
+        assert_rendered!(text);
+    }
+
+    #[test]
+    fn convert_markdown_image_placeholder() {
+        let md = "before ![a diagram](images/missing.png) after\n\n![](images/logo.png)";
+        let text = convert_md(md);
+        let lines: Vec<String> = text.lines.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "before [image: a diagram] after",
+                "",
+                "[image: logo.png]",
+                ""
+            ]
+        );
+    }
+
+    #[test]
+    fn image_half_blocks() {
+        const RED: Option<[u8; 3]> = Some([255, 0, 0]);
+        const BLUE: Option<[u8; 3]> = Some([0, 0, 255]);
+        let image = Image {
+            path: "test.png",
+            width: 2,
+            pixels: &[RED, None, BLUE, BLUE, None, RED],
+        };
+        let lines = image_lines(&image, 10);
+        let cells: Vec<Vec<_>> = lines
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|s| (s.content.as_ref(), s.style.fg, s.style.bg))
+                    .collect()
+            })
+            .collect();
+        let red = Some(Color::Rgb(255, 0, 0));
+        let blue = Some(Color::Rgb(0, 0, 255));
+        assert_eq!(
+            cells,
+            vec![
+                vec![("▀", red, blue), ("▄", blue, None)],
+                vec![(" ", None, None), ("▀", red, None)],
+            ]
+        );
+        // shrunk to fit a narrower view
+        assert_eq!(image_lines(&image, 1)[0].width(), 1);
+    }
+
+    #[test]
+    fn convert_markdown_definitions_and_front_matter() {
+        let md = r#"---
+tags: [rust, lua]
+---
+Rust
+: A systems language with a definition long enough to wrap
+
+Lua
+: An embeddable language
+"#;
+        let doc = convert_document(md, false, Some(40));
+        assert_eq!(
+            doc.metadata,
+            vec![Field {
+                key: "tags".to_string(),
+                values: vec!["rust".to_string(), "lua".to_string()],
+            }]
+        );
+        assert_rendered!(doc.text);
+    }
+
+    #[test]
+    fn convert_markdown_math() {
+        let md = r#"Inline $\alpha^2 \leq \beta_1$ and $\unknown{x}$.
+
+$$
+\frac{a + b}{2} \cdot \pi
+$$
+
+$$\begin{matrix} a \end{matrix}$$
+"#;
+        assert_rendered!(convert_document(md, false, Some(45)).text);
+    }
+
+    #[test]
+    fn convert_markdown_html() {
+        let md = r#"Press <kbd>Ctrl</kbd>+<kbd>C</kbd> to <b>copy</b><br>and <u>paste</u> <!-- a comment --><span>here</span>
+
+<script>alert("no")</script>
+
+<details>
+<summary>More</summary>
+
+Hidden until expanded
+
+</details>
+
+After
+"#;
+        let collapsed = convert_document(md, false, Some(45));
+        let toggles: Vec<_> = collapsed.links.iter().map(|l| l.toggle).collect();
+        assert_eq!(toggles, vec![Some(0)]);
+        let lines: Vec<String> = collapsed
+            .text
+            .lines
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                "Press [Ctrl]+[C] to copy",
+                "and paste here",
+                "",
+                "▶ More",
+                "",
+                "After",
+                "",
+            ]
+        );
+        assert_rendered!(convert_toggled(md, false, Some(45), &[0], &Theme::DEFAULT).text);
+    }
+
+    #[test]
+    fn convert_markdown_theme() {
+        let md = "### Heading\n\n```rust\nfn main() {}\n```\n";
+        let theme = Theme {
+            keyword: Color::Blue,
+            heading: Color::Red,
+            ..Theme::DEFAULT
+        };
+        let text = convert_toggled(md, false, Some(40), &[], &theme).text;
+        assert_eq!(text.lines[0].spans[0].style.fg, Some(Color::Red));
+        let keyword = text
+            .lines
+            .iter()
+            .flat_map(|line| &line.spans)
+            .find(|span| span.content == "fn")
+            .unwrap();
+        assert_eq!(keyword.style.fg, Some(Color::Blue));
+    }
+
+    #[test]
+    fn convert_markdown_html_multibyte() {
+        let md = "<div>\né text\n</div>\n";
+        let lines: Vec<String> = convert_document(md, false, Some(45))
+            .text
+            .lines
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(lines[0].trim_end(), "é text");
+    }
+
+    #[test]
+    fn convert_markdown_table() {
+        let md = r#"| Language | Years | Notes |
+|:---------|------:|:-----:|
+| Rust | 8 | The language this resume is written in, including the browser build |
+| Lua | 5 | **Sandboxed** |
+"#;
+        assert_rendered!(convert_document(md, false, Some(45)).text);
+    }
+
+    #[test]
+    fn convert_markdown_narrow_table() {
+        let md = "| A | B |\n|---|---|\n| one | two |\n";
+        for width in 1..=9 {
+            let text = convert_document(md, false, Some(width)).text;
+            assert!(
+                text.lines.iter().all(|line| line.width() <= width),
+                "{width}: {text:?}"
+            );
+        }
+        let lines: Vec<String> = convert_document(md, false, Some(8))
+            .text
+            .lines
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(lines, vec!["A: one", "B: two", ""]);
+    }
+
+    #[test]
+    fn convert_markdown_nested_lists() {
+        let md = r#"- one
+  - nested
+    1. first
+    2. second
+       has a break\
+       here
+  - [x] done
+  - [ ] todo
+- two
+
+3. three
+4. four
+"#;
         assert_rendered!(convert_md(md));
     }
 
+    #[test]
+    fn convert_markdown_headings() {
+        let md = "# One\n\n## Two\n\n### Three\n\n#### Four\n\n##### Five\n\n###### Six";
+        let doc = convert_document(md, false, None);
+        let headings: Vec<_> = doc
+            .headings
+            .iter()
+            .map(|h| (h.level, h.text.as_str(), h.line))
+            .collect();
+        assert_eq!(
+            headings,
+            vec![
+                (1, "One", 0),
+                (2, "Two", 2),
+                (3, "Three", 4),
+                (4, "Four", 5),
+                (5, "FIVE", 6),
+                (6, "SIX", 7),
+            ]
+        );
+        assert_rendered!(doc.text);
+    }
+
     #[test]
     fn convert_markdown_one_line() {
         env_logger::builder().is_test(true).try_init().ok();
@@ -0,0 +1,495 @@
+//! Approximating LaTeX math with Unicode.
+//!
+//! The commands that show up in prose, Greek letters, operators, fractions,
+//! roots and sub/superscripts, are converted to their Unicode equivalents.
+//! Anything else can't be displayed faithfully so `None` is returned and the
+//! caller shows the raw TeX instead.
+
+use unicode_width::UnicodeWidthStr;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Text(String),
+    Frac(Vec<Node>, Vec<Node>),
+    Sqrt(Vec<Node>),
+    Sup(Vec<Node>),
+    Sub(Vec<Node>),
+}
+
+/// Convert inline math to a single line
+pub(crate) fn inline(tex: &str) -> Option<String> {
+    Some(render_inline(&Parser::new(tex).parse()?))
+}
+
+/// Convert display math to one or more lines, fractions are drawn with the
+/// numerator above the denominator
+pub(crate) fn display(tex: &str) -> Option<Vec<String>> {
+    let block = render_block(&Parser::new(tex).parse()?);
+    Some(block.lines)
+}
+
+struct Parser<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    fn new(tex: &'a str) -> Self {
+        Self { rest: tex.trim() }
+    }
+
+    fn parse(mut self) -> Option<Vec<Node>> {
+        let nodes = self.nodes()?;
+        self.rest.is_empty().then_some(nodes)
+    }
+
+    /// Parse until the end of the input or the end of the current group
+    fn nodes(&mut self) -> Option<Vec<Node>> {
+        let mut ret = Vec::new();
+        while !self.rest.is_empty() && !self.rest.starts_with('}') {
+            let ch = self.next_char()?;
+            match ch {
+                '^' => ret.push(Node::Sup(self.atom()?)),
+                '_' => ret.push(Node::Sub(self.atom()?)),
+                '{' => ret.extend(self.group_rest()?),
+                '\\' => ret.extend(self.command()?),
+                '&' | '#' | '%' | '$' => return None,
+                ch if ch.is_whitespace() => {
+                    // runs of whitespace are collapsed
+                    self.rest = self.rest.trim_start();
+                    ret.push(Node::Text(" ".to_string()));
+                }
+                ch => ret.push(Node::Text(ch.to_string())),
+            }
+        }
+        Some(ret)
+    }
+
+    fn next_char(&mut self) -> Option<char> {
+        let ch = self.rest.chars().next()?;
+        self.rest = &self.rest[ch.len_utf8()..];
+        Some(ch)
+    }
+
+    /// The rest of a group after its `{`
+    fn group_rest(&mut self) -> Option<Vec<Node>> {
+        let nodes = self.nodes()?;
+        self.rest = self.rest.strip_prefix('}')?;
+        Some(nodes)
+    }
+
+    /// A required argument, either a group or a single character or command
+    fn atom(&mut self) -> Option<Vec<Node>> {
+        self.rest = self.rest.trim_start();
+        match self.next_char()? {
+            '{' => self.group_rest(),
+            '\\' => self.command(),
+            ch if ch.is_whitespace() || "}^_".contains(ch) => None,
+            ch => Some(vec![Node::Text(ch.to_string())]),
+        }
+    }
+
+    /// The raw text of a group, for `\text` and similar
+    fn raw_group(&mut self) -> Option<String> {
+        self.rest = self.rest.trim_start().strip_prefix('{')?;
+        let end = self.rest.find('}')?;
+        let text = self.rest[..end].to_string();
+        self.rest = &self.rest[end + 1..];
+        Some(text)
+    }
+
+    /// A command after its `\`
+    fn command(&mut self) -> Option<Vec<Node>> {
+        let len = self
+            .rest
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(self.rest.len());
+        if len == 0 {
+            // a single character command like `\{` or `\,`
+            let text = match self.next_char()? {
+                ',' | ';' | ':' | ' ' => " ",
+                '!' => "",
+                '{' => "{",
+                '}' => "}",
+                '|' => "‖",
+                '%' => "%",
+                '$' => "$",
+                '&' => "&",
+                '#' => "#",
+                '_' => "_",
+                _ => return None,
+            };
+            return Some(vec![Node::Text(text.to_string())]);
+        }
+        let name = &self.rest[..len];
+        self.rest = &self.rest[len..];
+        let node = match name {
+            "frac" | "dfrac" | "tfrac" => Node::Frac(self.atom()?, self.atom()?),
+            "sqrt" => Node::Sqrt(self.atom()?),
+            "text" | "textrm" | "mathrm" | "mathit" | "mathbf" | "operatorname" => {
+                Node::Text(self.raw_group()?)
+            }
+            "mathbb" => {
+                let group = self.raw_group()?;
+                let text = group
+                    .trim()
+                    .chars()
+                    .map(double_struck)
+                    .collect::<Option<String>>()?;
+                Node::Text(text)
+            }
+            // the delimiter after these is displayed as is, `.` is no delimiter
+            "left" | "right" | "big" | "Big" | "bigg" | "Bigg" => {
+                self.rest = self.rest.trim_start();
+                if let Some(rest) = self.rest.strip_prefix('.') {
+                    self.rest = rest;
+                }
+                return Some(Vec::new());
+            }
+            "quad" | "qquad" => Node::Text(" ".to_string()),
+            "sin" | "cos" | "tan" | "log" | "ln" | "exp" | "lim" | "max" | "min" | "det"
+            | "gcd" | "mod" => Node::Text(name.to_string()),
+            name => Node::Text(symbol(name)?.to_string()),
+        };
+        Some(vec![node])
+    }
+}
+
+fn symbol(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "alpha" => "α",
+        "beta" => "β",
+        "gamma" => "γ",
+        "delta" => "δ",
+        "epsilon" => "ϵ",
+        "varepsilon" => "ε",
+        "zeta" => "ζ",
+        "eta" => "η",
+        "theta" => "θ",
+        "vartheta" => "ϑ",
+        "iota" => "ι",
+        "kappa" => "κ",
+        "lambda" => "λ",
+        "mu" => "μ",
+        "nu" => "ν",
+        "xi" => "ξ",
+        "pi" => "π",
+        "varpi" => "ϖ",
+        "rho" => "ρ",
+        "sigma" => "σ",
+        "tau" => "τ",
+        "upsilon" => "υ",
+        "phi" => "ϕ",
+        "varphi" => "φ",
+        "chi" => "χ",
+        "psi" => "ψ",
+        "omega" => "ω",
+        "Gamma" => "Γ",
+        "Delta" => "Δ",
+        "Theta" => "Θ",
+        "Lambda" => "Λ",
+        "Xi" => "Ξ",
+        "Pi" => "Π",
+        "Sigma" => "Σ",
+        "Upsilon" => "Υ",
+        "Phi" => "Φ",
+        "Psi" => "Ψ",
+        "Omega" => "Ω",
+        "times" => "×",
+        "cdot" => "·",
+        "div" => "÷",
+        "pm" => "±",
+        "mp" => "∓",
+        "ast" => "∗",
+        "star" => "⋆",
+        "circ" => "∘",
+        "leq" | "le" => "≤",
+        "geq" | "ge" => "≥",
+        "neq" | "ne" => "≠",
+        "approx" => "≈",
+        "equiv" => "≡",
+        "sim" => "∼",
+        "propto" => "∝",
+        "ll" => "≪",
+        "gg" => "≫",
+        "infty" => "∞",
+        "sum" => "∑",
+        "prod" => "∏",
+        "int" => "∫",
+        "oint" => "∮",
+        "partial" => "∂",
+        "nabla" => "∇",
+        "to" | "rightarrow" => "→",
+        "leftarrow" | "gets" => "←",
+        "leftrightarrow" => "↔",
+        "Rightarrow" | "implies" => "⇒",
+        "Leftarrow" => "⇐",
+        "Leftrightarrow" | "iff" => "⇔",
+        "mapsto" => "↦",
+        "in" => "∈",
+        "notin" => "∉",
+        "ni" => "∋",
+        "subset" => "⊂",
+        "subseteq" => "⊆",
+        "supset" => "⊃",
+        "supseteq" => "⊇",
+        "cup" => "∪",
+        "cap" => "∩",
+        "setminus" => "∖",
+        "emptyset" | "varnothing" => "∅",
+        "forall" => "∀",
+        "exists" => "∃",
+        "neg" | "lnot" => "¬",
+        "land" | "wedge" => "∧",
+        "lor" | "vee" => "∨",
+        "oplus" => "⊕",
+        "otimes" => "⊗",
+        "ldots" | "dots" => "…",
+        "cdots" => "⋯",
+        "vdots" => "⋮",
+        "prime" => "′",
+        "degree" => "°",
+        "langle" => "⟨",
+        "rangle" => "⟩",
+        "lceil" => "⌈",
+        "rceil" => "⌉",
+        "lfloor" => "⌊",
+        "rfloor" => "⌋",
+        "hbar" => "ℏ",
+        "ell" => "ℓ",
+        "Re" => "ℜ",
+        "Im" => "ℑ",
+        "aleph" => "ℵ",
+        _ => return None,
+    })
+}
+
+fn double_struck(ch: char) -> Option<char> {
+    Some(match ch {
+        'C' => 'ℂ',
+        'N' => 'ℕ',
+        'P' => 'ℙ',
+        'Q' => 'ℚ',
+        'R' => 'ℝ',
+        'Z' => 'ℤ',
+        _ => return None,
+    })
+}
+
+fn superscript(ch: char) -> Option<char> {
+    const LETTERS: &str = "ᵃᵇᶜᵈᵉᶠᵍʰⁱʲᵏˡᵐⁿᵒᵖ ʳˢᵗᵘᵛʷˣʸᶻ";
+    Some(match ch {
+        '0' => '⁰',
+        '1' => '¹',
+        '2' => '²',
+        '3' => '³',
+        '4' => '⁴',
+        '5' => '⁵',
+        '6' => '⁶',
+        '7' => '⁷',
+        '8' => '⁸',
+        '9' => '⁹',
+        '+' => '⁺',
+        '-' | '−' => '⁻',
+        '=' => '⁼',
+        '(' => '⁽',
+        ')' => '⁾',
+        ' ' => ' ',
+        '′' => '′',
+        'a'..='z' => LETTERS
+            .chars()
+            .nth(ch as usize - 'a' as usize)
+            .filter(|c| *c != ' ')?,
+        _ => return None,
+    })
+}
+
+fn subscript(ch: char) -> Option<char> {
+    Some(match ch {
+        '0' => '₀',
+        '1' => '₁',
+        '2' => '₂',
+        '3' => '₃',
+        '4' => '₄',
+        '5' => '₅',
+        '6' => '₆',
+        '7' => '₇',
+        '8' => '₈',
+        '9' => '₉',
+        '+' => '₊',
+        '-' | '−' => '₋',
+        '=' => '₌',
+        '(' => '₍',
+        ')' => '₎',
+        ' ' => ' ',
+        'a' => 'ₐ',
+        'e' => 'ₑ',
+        'h' => 'ₕ',
+        'i' => 'ᵢ',
+        'j' => 'ⱼ',
+        'k' => 'ₖ',
+        'l' => 'ₗ',
+        'm' => 'ₘ',
+        'n' => 'ₙ',
+        'o' => 'ₒ',
+        'p' => 'ₚ',
+        'r' => 'ᵣ',
+        's' => 'ₛ',
+        't' => 'ₜ',
+        'u' => 'ᵤ',
+        'v' => 'ᵥ',
+        'x' => 'ₓ',
+        _ => return None,
+    })
+}
+
+/// Wrap anything longer than a single character in parentheses so it reads
+/// as one term, e.g. the numerator of an inline fraction
+fn term(text: String) -> String {
+    if text.chars().count() > 1 && !text.chars().all(char::is_alphanumeric) {
+        format!("({text})")
+    } else {
+        text
+    }
+}
+
+fn render_inline(nodes: &[Node]) -> String {
+    let mut ret = String::new();
+    for node in nodes {
+        match node {
+            Node::Text(text) => ret.push_str(text),
+            Node::Frac(num, den) => {
+                ret.push_str(&term(render_inline(num)));
+                ret.push('/');
+                ret.push_str(&term(render_inline(den)));
+            }
+            Node::Sqrt(inner) => {
+                ret.push('√');
+                ret.push_str(&term(render_inline(inner)));
+            }
+            Node::Sup(inner) => ret.push_str(&script(inner, '^', superscript)),
+            Node::Sub(inner) => ret.push_str(&script(inner, '_', subscript)),
+        }
+    }
+    ret
+}
+
+/// A superscript or subscript, when any character doesn't have a small
+/// version the TeX style `^(..)` is used instead
+fn script(inner: &[Node], marker: char, convert: fn(char) -> Option<char>) -> String {
+    let text = render_inline(inner);
+    text.chars()
+        .map(convert)
+        .collect::<Option<String>>()
+        .unwrap_or_else(|| format!("{marker}{}", term(text)))
+}
+
+/// Lines of text that are placed next to each other lined up by their
+/// `baseline`, the row operators and plain text are on
+#[derive(Debug)]
+struct Block {
+    lines: Vec<String>,
+    baseline: usize,
+}
+
+impl Block {
+    fn text(text: String) -> Self {
+        Self {
+            lines: vec![text],
+            baseline: 0,
+        }
+    }
+
+    fn width(&self) -> usize {
+        self.lines
+            .iter()
+            .map(|l| l.width())
+            .max()
+            .unwrap_or_default()
+    }
+
+    fn below(&self) -> usize {
+        self.lines.len() - self.baseline - 1
+    }
+}
+
+fn center(text: &str, width: usize) -> String {
+    let pad = width.saturating_sub(text.width());
+    format!("{}{text}{}", " ".repeat(pad / 2), " ".repeat(pad - pad / 2))
+}
+
+fn render_block(nodes: &[Node]) -> Block {
+    let mut blocks = Vec::new();
+    let mut text = String::new();
+    for node in nodes {
+        let Node::Frac(num, den) = node else {
+            text.push_str(&render_inline(std::slice::from_ref(node)));
+            continue;
+        };
+        if !text.is_empty() {
+            blocks.push(Block::text(core::mem::take(&mut text)));
+        }
+        let (num, den) = (render_block(num), render_block(den));
+        let width = num.width().max(den.width());
+        let mut lines: Vec<String> = num.lines.iter().map(|l| center(l, width)).collect();
+        lines.push("─".repeat(width));
+        lines.extend(den.lines.iter().map(|l| center(l, width)));
+        blocks.push(Block {
+            baseline: num.lines.len(),
+            lines,
+        });
+    }
+    if !text.is_empty() || blocks.is_empty() {
+        blocks.push(Block::text(text));
+    }
+    let above = blocks.iter().map(|b| b.baseline).max().unwrap_or_default();
+    let below = blocks.iter().map(Block::below).max().unwrap_or_default();
+    let lines = (0..above + below + 1)
+        .map(|row| {
+            let line: String = blocks
+                .iter()
+                .map(|block| {
+                    let width = block.width();
+                    let idx = (row + block.baseline).checked_sub(above);
+                    let line = idx
+                        .and_then(|idx| block.lines.get(idx))
+                        .map(String::as_str)
+                        .unwrap_or_default();
+                    format!("{line}{}", " ".repeat(width - line.width()))
+                })
+                .collect();
+            line.trim_end().to_string()
+        })
+        .collect();
+    Block {
+        lines,
+        baseline: above,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn inline_math() {
+        assert_eq!(
+            inline(r"\alpha^2 + \beta_{i} \leq \frac{a+b}{2}").as_deref(),
+            Some("α² + βᵢ ≤ (a+b)/2")
+        );
+        assert_eq!(inline(r"x^{q}").as_deref(), Some("x^q"));
+        assert_eq!(
+            inline(r"\sqrt{x} \in \mathbb{R}").as_deref(),
+            Some("√x ∈ ℝ")
+        );
+        assert_eq!(inline(r"\unknown{x}"), None);
+        assert_eq!(inline(r"\frac{a}"), None);
+    }
+
+    #[test]
+    fn display_fraction() {
+        assert_eq!(
+            display(r"f(x) = \frac{x^2 + 1}{\pi} + c").unwrap(),
+            vec!["       x² + 1", "f(x) = ────── + c", "         π"]
+        );
+    }
+}
@@ -8,23 +8,25 @@ use ratatui::{
 };
 
 use crate::{
-    data::{source::DATABASE, Project},
-    detail_view::DetailView,
-    list_state::ListStateWrapper as ListState,
-    Navigable, DEFAULT_STYLE,
+    data::Project, detail_view::DetailView, list_state::ListStateWrapper as ListState, Action,
+    Context, Navigable, Page, DEFAULT_STYLE,
 };
 
 #[derive(Debug, Clone)]
 pub struct OssView<'a> {
+    projects: &'a [Project<'a>],
     menu: ListState,
     sub_page: Option<ProjectView<'a>>,
+    context: Context,
 }
 
-impl<'a> Default for OssView<'a> {
-    fn default() -> Self {
+impl<'a> OssView<'a> {
+    pub fn new(projects: &'a [Project<'a>], context: Context) -> Self {
         Self {
-            menu: ListState::new(DATABASE.open_source.len().saturating_sub(1)),
+            projects,
+            menu: ListState::new(projects.len().saturating_sub(1)),
             sub_page: None,
+            context,
         }
     }
 }
@@ -38,8 +40,8 @@ impl<'a> Widget for OssView<'a> {
             sub_page.render(area, buf);
             return;
         }
-        let list_items: Vec<ListItem> = DATABASE
-            .open_source
+        let list_items: Vec<ListItem> = self
+            .projects
             .iter()
             .map(|w| ListItem::new(Text::from(format!("{}\n    {}", w.name, w.short_desc,))))
             .collect();
@@ -67,6 +69,14 @@ impl<'a> Navigable for OssView<'a> {
         self.menu.decrement()
     }
 
+    fn select_item(&mut self, idx: usize) {
+        if let Some(sub_page) = self.sub_page.as_mut() {
+            sub_page.select_item(idx);
+            return;
+        }
+        self.menu.select_item(idx);
+    }
+
     fn handle_enter(&mut self) {
         if let Some(sub_page) = self.sub_page.as_mut() {
             sub_page.handle_enter();
@@ -75,7 +85,10 @@ impl<'a> Navigable for OssView<'a> {
         let Some(idx) = self.menu.selected() else {
             return;
         };
-        self.sub_page = DATABASE.open_source.get(idx).map(Into::into);
+        self.sub_page = self
+            .projects
+            .get(idx)
+            .map(|project| ProjectView::new(project, self.context.clone()));
     }
 
     fn handle_left(&mut self) -> bool {
@@ -87,29 +100,62 @@ impl<'a> Navigable for OssView<'a> {
         }
         false
     }
+
+    fn focus_next_link(&mut self) {
+        if let Some(sub_page) = self.sub_page.as_mut() {
+            sub_page.focus_next_link();
+        }
+    }
+
+    fn jump_to_heading(&mut self, forward: bool) {
+        if let Some(sub_page) = self.sub_page.as_mut() {
+            sub_page.jump_to_heading(forward);
+        }
+    }
+
+    fn fit_to_view(&mut self) {
+        if let Some(sub_page) = self.sub_page.as_mut() {
+            sub_page.fit_to_view();
+        }
+    }
+
+    fn take_action(&mut self) -> Option<Action> {
+        self.sub_page.as_mut()?.take_action()
+    }
+
+    fn yank(&self) -> Option<String> {
+        if let Some(sub_page) = self.sub_page.as_ref() {
+            return sub_page.yank();
+        }
+        let project = self.projects.get(self.menu.selected()?)?;
+        Some(project.long_desc.to_string())
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct ProjectView<'a> {
-    project: Project,
+    project: Project<'a>,
     long_desc: DetailView<'a>,
     menu: ListState,
     sub_page: Option<SubProjectView<'a>>,
+    context: Context,
 }
 
 #[derive(Debug, Clone)]
 pub enum SubProjectView<'a> {
     SubProject(Box<ProjectView<'a>>),
-    LongDescription(DetailView<'a>),
+    LongDescription(Box<DetailView<'a>>),
 }
 
-impl<'a> From<&Project> for ProjectView<'a> {
-    fn from(value: &Project) -> Self {
+impl<'a> ProjectView<'a> {
+    pub fn new(project: &Project<'a>, context: Context) -> Self {
         Self {
-            project: value.clone(),
-            long_desc: DetailView::new("Detailed Description", value.long_desc),
-            menu: ListState::new(value.sub_projects.len() + 1),
+            project: project.clone(),
+            long_desc: DetailView::new("Detailed Description", project.long_desc, context.clone()),
+            // The second row is the un-selectable "Projects" header
+            menu: ListState::with_skipped(project.sub_projects.len() + 1, vec![1]),
             sub_page: None,
+            context,
         }
     }
 }
@@ -188,9 +234,6 @@ impl<'a> Navigable for ProjectView<'a> {
                 return;
             }
             self.menu.increment();
-            if self.menu.selected() == Some(1) {
-                self.menu.increment();
-            }
             return;
         };
         match sub_page {
@@ -206,9 +249,6 @@ impl<'a> Navigable for ProjectView<'a> {
                 return;
             }
             self.menu.decrement();
-            if self.menu.selected() == Some(1) {
-                self.menu.decrement();
-            }
             return;
         };
         match sub_page {
@@ -217,28 +257,37 @@ impl<'a> Navigable for ProjectView<'a> {
         }
     }
 
+    fn select_item(&mut self, idx: usize) {
+        match self.sub_page.as_mut() {
+            Some(sub_page) => sub_page.select_item(idx),
+            None if self.project.sub_projects.is_empty() => self.long_desc.select_item(idx),
+            None => self.menu.select_item(idx),
+        }
+    }
+
     fn handle_enter(&mut self) {
         if let Some(sub_page) = self.sub_page.as_mut() {
-            if let SubProjectView::SubProject(sp) = sub_page {
-                sp.handle_enter();
-            }
+            sub_page.handle_enter();
+            return;
+        }
+        if self.project.sub_projects.is_empty() && self.long_desc.link_focused() {
+            self.long_desc.handle_enter();
             return;
         }
         let Some(idx) = self.menu.selected() else {
             return;
         };
         if idx == 0 {
-            self.sub_page = Some(SubProjectView::LongDescription(DetailView::new(
+            self.sub_page = Some(SubProjectView::LongDescription(Box::new(DetailView::new(
                 self.project.name,
                 self.project.long_desc,
-            )));
+                self.context.clone(),
+            ))));
         } else if let Some(sub_project) = self.project.sub_projects.get(idx - 2).cloned() {
-            self.sub_page = Some(SubProjectView::SubProject(Box::new(ProjectView {
-                menu: ListState::new(sub_project.sub_projects.len()),
-                long_desc: DetailView::new("Detailed Description", sub_project.long_desc),
-                project: sub_project,
-                sub_page: None,
-            })))
+            self.sub_page = Some(SubProjectView::SubProject(Box::new(ProjectView::new(
+                &sub_project,
+                self.context.clone(),
+            ))))
         }
     }
 
@@ -251,6 +300,57 @@ impl<'a> Navigable for ProjectView<'a> {
         }
         false
     }
+
+    fn focus_next_link(&mut self) {
+        match self.sub_page.as_mut() {
+            Some(sub_page) => sub_page.focus_next_link(),
+            None if self.project.sub_projects.is_empty() => self.long_desc.focus_next_link(),
+            None => {}
+        }
+    }
+
+    fn jump_to_heading(&mut self, forward: bool) {
+        match self.sub_page.as_mut() {
+            Some(sub_page) => sub_page.jump_to_heading(forward),
+            None if self.project.sub_projects.is_empty() => self.long_desc.jump_to_heading(forward),
+            None => {}
+        }
+    }
+
+    fn fit_to_view(&mut self) {
+        match self.sub_page.as_mut() {
+            Some(sub_page) => sub_page.fit_to_view(),
+            None if self.project.sub_projects.is_empty() => self.long_desc.fit_to_view(),
+            None => {}
+        }
+    }
+
+    fn take_action(&mut self) -> Option<Action> {
+        match self.sub_page.as_mut() {
+            Some(sub_page) => sub_page.take_action(),
+            None if self.project.sub_projects.is_empty() => self.long_desc.take_action(),
+            None => None,
+        }
+    }
+
+    fn yank(&self) -> Option<String> {
+        if let Some(sub_page) = self.sub_page.as_ref() {
+            return sub_page.yank();
+        }
+        if self.project.sub_projects.is_empty() {
+            return self.long_desc.yank();
+        }
+        let long_desc = match self.menu.selected()? {
+            0 => self.project.long_desc,
+            idx => {
+                self.project
+                    .sub_projects
+                    .get(idx.checked_sub(2)?)?
+                    .long_desc
+            }
+        };
+        Some(long_desc.to_string())
+    }
 }
 
 impl<'a> Navigable for SubProjectView<'a> {
@@ -268,11 +368,18 @@ impl<'a> Navigable for SubProjectView<'a> {
         }
     }
 
+    fn select_item(&mut self, idx: usize) {
+        match self {
+            SubProjectView::LongDescription(inner) => inner.select_item(idx),
+            SubProjectView::SubProject(inner) => inner.select_item(idx),
+        }
+    }
+
     fn handle_enter(&mut self) {
-        let SubProjectView::SubProject(inner) = self else {
-            return;
-        };
-        inner.handle_enter()
+        match self {
+            SubProjectView::LongDescription(inner) => inner.handle_enter(),
+            SubProjectView::SubProject(inner) => inner.handle_enter(),
+        }
     }
 
     fn handle_left(&mut self) -> bool {
@@ -281,12 +388,47 @@ impl<'a> Navigable for SubProjectView<'a> {
         };
         inner.handle_left()
     }
+
+    fn focus_next_link(&mut self) {
+        match self {
+            SubProjectView::LongDescription(inner) => inner.focus_next_link(),
+            SubProjectView::SubProject(inner) => inner.focus_next_link(),
+        }
+    }
+
+    fn jump_to_heading(&mut self, forward: bool) {
+        match self {
+            SubProjectView::LongDescription(inner) => inner.jump_to_heading(forward),
+            SubProjectView::SubProject(inner) => inner.jump_to_heading(forward),
+        }
+    }
+
+    fn fit_to_view(&mut self) {
+        match self {
+            SubProjectView::LongDescription(inner) => inner.fit_to_view(),
+            SubProjectView::SubProject(inner) => inner.fit_to_view(),
+        }
+    }
+
+    fn take_action(&mut self) -> Option<Action> {
+        match self {
+            SubProjectView::LongDescription(inner) => inner.take_action(),
+            SubProjectView::SubProject(inner) => inner.take_action(),
+        }
+    }
+
+    fn yank(&self) -> Option<String> {
+        match self {
+            SubProjectView::LongDescription(inner) => inner.yank(),
+            SubProjectView::SubProject(inner) => inner.yank(),
+        }
+    }
 }
 
-fn render_two_blocks(
+fn render_two_blocks<'a>(
     area: Rect,
     buf: &mut Buffer,
-    details: impl Iterator<Item = (&'static str, &'static str)>,
+    details: impl Iterator<Item = (&'a str, &'a str)>,
 ) {
     let borders = [
         (
@@ -305,11 +447,7 @@ fn render_two_blocks(
         ),
     ];
     let cells: [Rect; 2] = Layout::horizontal(Constraint::from_percentages([50; 2])).areas(area);
-    for ((cell, (title, content)), (borders, set)) in cells
-        .into_iter()
-        .zip(details.into_iter())
-        .zip(borders.into_iter())
-    {
+    for ((cell, (title, content)), (borders, set)) in cells.into_iter().zip(details).zip(borders) {
         render_block(cell, buf, title, content, borders, set);
     }
 }
@@ -317,8 +455,8 @@ fn render_two_blocks(
 fn render_block(
     area: Rect,
     buf: &mut Buffer,
-    title: &'static str,
-    content: &'static str,
+    title: &str,
+    content: &str,
     border: Borders,
     set: Set,
 ) {
@@ -333,3 +471,22 @@ fn render_block(
     let content = crate::markdown::convert_md(content);
     Paragraph::new(content).render(rect, buf);
 }
+
+impl<'a> Page for OssView<'a> {
+    fn title(&self) -> &str {
+        "Open Source"
+    }
+
+    fn id(&self) -> &str {
+        "oss"
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        self.fit_to_view();
+        Widget::render(self.clone(), area, buf);
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(self.projects, self.context.clone());
+    }
+}
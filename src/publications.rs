@@ -0,0 +1,301 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    widgets::{List, ListItem, StatefulWidget, Widget},
+};
+
+use crate::{
+    data::{Publication, Talk},
+    date::Date,
+    detail_view::DetailView,
+    list_state::ListStateWrapper as ListState,
+    work::headline_list_item,
+    Action, Context, Navigable, Page, DEFAULT_STYLE,
+};
+
+/// The shared interface for items displayed in a [`CitationsView`]
+pub trait Citation: std::fmt::Debug + Clone {
+    /// The menu title of the page listing these items
+    const PAGE_TITLE: &'static str;
+    /// The route id of the page listing these items
+    const PAGE_ID: &'static str;
+    fn title(&self) -> &str;
+    fn venue(&self) -> &str;
+    fn date(&self) -> Date;
+    fn summary(&self) -> &str;
+    /// A formatted citation for this item as markdown, `author` is the name
+    /// of the person the resume is for.
+    fn citation(&self, author: &str) -> String;
+}
+
+impl<'a> Citation for Publication<'a> {
+    const PAGE_TITLE: &'static str = "Publications";
+    const PAGE_ID: &'static str = "publications";
+
+    fn title(&self) -> &str {
+        self.title
+    }
+
+    fn venue(&self) -> &str {
+        self.venue
+    }
+
+    fn date(&self) -> Date {
+        self.date
+    }
+
+    fn summary(&self) -> &str {
+        self.summary
+    }
+
+    fn citation(&self, author: &str) -> String {
+        let mut ret = format!(
+            "{} ({}). {}. *{}*.",
+            authors(author, self.co_authors),
+            self.date,
+            self.title,
+            self.venue,
+        );
+        if let Some(url) = self.url {
+            ret.push(' ');
+            ret.push_str(url);
+        }
+        ret
+    }
+}
+
+impl<'a> Citation for Talk<'a> {
+    const PAGE_TITLE: &'static str = "Talks";
+    const PAGE_ID: &'static str = "talks";
+
+    fn title(&self) -> &str {
+        self.title
+    }
+
+    fn venue(&self) -> &str {
+        self.venue
+    }
+
+    fn date(&self) -> Date {
+        self.date
+    }
+
+    fn summary(&self) -> &str {
+        self.summary
+    }
+
+    fn citation(&self, author: &str) -> String {
+        let mut ret = format!(
+            "{} ({}). *{}* [Talk]. {}.",
+            authors(author, self.co_authors),
+            self.date,
+            self.title,
+            self.venue,
+        );
+        if let Some(url) = self.url {
+            ret.push(' ');
+            ret.push_str(url);
+        }
+        ret
+    }
+}
+
+/// Join the author list in the form `A`, `A & B` or `A, B, & C`
+fn authors(author: &str, co_authors: &[&str]) -> String {
+    let mut ret = author.to_string();
+    for (idx, co_author) in co_authors.iter().enumerate() {
+        if idx + 1 == co_authors.len() {
+            if co_authors.len() > 1 {
+                ret.push(',');
+            }
+            ret.push_str(" & ");
+        } else {
+            ret.push_str(", ");
+        }
+        ret.push_str(co_author);
+    }
+    ret
+}
+
+/// A list of publications or talks, selecting one displays its citation
+/// followed by the abstract
+#[derive(Debug, Clone)]
+pub struct CitationsView<'a, T> {
+    author: &'a str,
+    items: &'a [T],
+    menu: ListState,
+    detail: Option<DetailView<'a>>,
+    context: Context,
+}
+
+impl<'a, T: Citation> CitationsView<'a, T> {
+    pub fn new(author: &'a str, items: &'a [T], context: Context) -> Self {
+        Self {
+            author,
+            items,
+            menu: ListState::new(items.len().saturating_sub(1)),
+            detail: None,
+            context,
+        }
+    }
+}
+
+impl<'a, T: Citation> Widget for CitationsView<'a, T> {
+    fn render(mut self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        if let Some(detail) = self.detail {
+            detail.render(area, buf);
+            return;
+        }
+        let list_items: Vec<ListItem> = self
+            .items
+            .iter()
+            .map(|item| {
+                headline_list_item(
+                    item.title(),
+                    format!("{}, {}", item.venue(), item.date()),
+                    None,
+                )
+            })
+            .collect();
+        let list = List::new(list_items)
+            .highlight_style(Style::new().bg(Color::Green).fg(Color::Black))
+            .style(DEFAULT_STYLE);
+        StatefulWidget::render(list, area, buf, self.menu.as_mut());
+    }
+}
+
+impl<'a, T: Citation> Navigable for CitationsView<'a, T> {
+    fn increment_selection(&mut self) {
+        if let Some(detail) = self.detail.as_mut() {
+            detail.increment_selection();
+            return;
+        }
+        self.menu.increment();
+    }
+
+    fn decrement_selection(&mut self) {
+        if let Some(detail) = self.detail.as_mut() {
+            detail.decrement_selection();
+            return;
+        }
+        self.menu.decrement();
+    }
+
+    fn select_item(&mut self, idx: usize) {
+        if let Some(detail) = self.detail.as_mut() {
+            detail.select_item(idx);
+            return;
+        }
+        self.menu.select_item(idx);
+    }
+
+    fn handle_enter(&mut self) {
+        if let Some(detail) = self.detail.as_mut() {
+            detail.handle_enter();
+            return;
+        }
+        let Some(item) = self.menu.selected().and_then(|idx| self.items.get(idx)) else {
+            return;
+        };
+        let content = format!(
+            "{}\n\n---\n\n{}",
+            item.citation(self.author),
+            item.summary()
+        );
+        self.detail = Some(DetailView::new(
+            item.title(),
+            &content,
+            self.context.clone(),
+        ));
+    }
+
+    fn handle_left(&mut self) -> bool {
+        self.detail.take().is_some()
+    }
+
+    fn focus_next_link(&mut self) {
+        if let Some(detail) = self.detail.as_mut() {
+            detail.focus_next_link();
+        }
+    }
+
+    fn jump_to_heading(&mut self, forward: bool) {
+        if let Some(detail) = self.detail.as_mut() {
+            detail.jump_to_heading(forward);
+        }
+    }
+
+    fn fit_to_view(&mut self) {
+        if let Some(detail) = self.detail.as_mut() {
+            detail.fit_to_view();
+        }
+    }
+
+    fn take_action(&mut self) -> Option<Action> {
+        self.detail.as_mut()?.take_action()
+    }
+
+    fn yank(&self) -> Option<String> {
+        if let Some(detail) = self.detail.as_ref() {
+            return detail.yank();
+        }
+        let item = self.items.get(self.menu.selected()?)?;
+        Some(item.citation(self.author))
+    }
+}
+
+impl<'a, T: Citation> Page for CitationsView<'a, T> {
+    fn title(&self) -> &str {
+        T::PAGE_TITLE
+    }
+
+    fn id(&self) -> &str {
+        T::PAGE_ID
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        self.fit_to_view();
+        Widget::render(self.clone(), area, buf);
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(self.author, self.items, self.context.clone());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn format_citations() {
+        let publication = Publication {
+            title: "A Paper",
+            venue: "A Journal",
+            date: Date::new(2020, 3),
+            co_authors: &["B", "C"],
+            url: None,
+            summary: "",
+        };
+        assert_eq!(
+            publication.citation("A"),
+            "A, B, & C (March 2020). A Paper. *A Journal*."
+        );
+        let talk = Talk {
+            title: "A Paper",
+            venue: "A Journal",
+            date: Date::year(2021),
+            co_authors: &["B"],
+            url: Some("https://a.example"),
+            summary: "",
+        };
+        assert_eq!(
+            talk.citation("A"),
+            "A & B (2021). *A Paper* [Talk]. A Journal. https://a.example"
+        );
+    }
+}
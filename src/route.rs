@@ -0,0 +1,211 @@
+//! Deep links into the application.
+//!
+//! A route is a `/` separated path resolved against a [`Database`], each
+//! segment is matched case-insensitively with any punctuation or whitespace
+//! treated as a `-`.
+//!
+//! - `home`
+//! - `work/<company>/<detail>` where detail is either the detail's headline
+//!   or its id (the number in the file name in the `job_details` directory)
+//! - `oss/<project>/<sub-project>/...`
+//! - `edu`
+//! - `publications/<title>`
+//! - `talks/<title>`
+//! - `<section title>/<entry>/<child entry>/...` for sections defined in
+//!   `sections.toml`
+//! - `<page id>` for any other page in the main menu, see
+//!   [`Page::id`](crate::Page::id)
+//!
+//! Everything after the first segment is optional, `work/smartthings` will
+//! open the list of details for that job.
+
+use crate::{
+    data::{Database, Project},
+    Error,
+};
+
+/// Resolve a route against a main menu of `pages`, the
+/// [`Page::id`](crate::Page::id)s in menu order, into the index of the page
+/// followed by the index of the item to open at each level below it. An
+/// empty path is the Home page.
+///
+/// Any page can be opened by the slug of its id but only the built in
+/// pages support routing to their content.
+pub fn resolve(db: &Database, pages: &[&str], route: &str) -> Result<Vec<usize>, Error> {
+    let mut segments = route
+        .trim_start_matches('#')
+        .split('/')
+        .filter(|s| !s.is_empty());
+    let mut path = Vec::new();
+    let Some(page) = segments.next() else {
+        return Ok(path);
+    };
+    let id = match slug(page).as_str() {
+        "home" => return Ok(path),
+        "open-source" => "oss".to_string(),
+        "education" => "edu".to_string(),
+        id => id.to_string(),
+    };
+    let (idx, page_id) = pages
+        .iter()
+        .enumerate()
+        .find(|(_, page_id)| slug(page_id) == id)
+        .ok_or_else(|| Error::Route(format!("unknown page `{page}`")))?;
+    path.push(idx);
+    match *page_id {
+        "work" => {
+            if let Some(company) = segments.next() {
+                let (idx, job) = find(db.jobs.iter(), company, |j| slug(j.name) == slug(company))?;
+                path.push(idx);
+                if let Some(detail) = segments.next() {
+                    let (idx, _) = find(job.all_details(), detail, |d| {
+                        slug(d.headline) == slug(detail) || d.id.to_string() == detail
+                    })?;
+                    path.push(idx);
+                }
+            }
+        }
+        "oss" => {
+            if let Some(name) = segments.next() {
+                let (idx, mut project) = find_project(db.open_source, name)?;
+                path.push(idx);
+                for name in segments.by_ref() {
+                    let (idx, sub_project) = find_project(project.sub_projects, name)?;
+                    // sub-projects are listed after the "Detailed Description"
+                    // entry and the un-selectable "Projects" header
+                    path.push(idx + 1);
+                    project = sub_project;
+                }
+            }
+        }
+        "publications" => {
+            if let Some(name) = segments.next() {
+                let (idx, _) = find(db.publications.iter(), name, |p| {
+                    slug(p.title) == slug(name)
+                })?;
+                path.push(idx);
+            }
+        }
+        "talks" => {
+            if let Some(name) = segments.next() {
+                let (idx, _) = find(db.talks.iter(), name, |t| slug(t.title) == slug(name))?;
+                path.push(idx);
+            }
+        }
+        _ => {
+            if let Some(section) = db.sections.iter().find(|s| s.title == *page_id) {
+                let mut entries = section.entries;
+                let mut has_overview = false;
+                for name in segments.by_ref() {
+                    let (idx, entry) =
+                        find(entries.iter(), name, |e| slug(e.headline) == slug(name))?;
+                    // entries with a body and children list the body first
+                    path.push(idx + has_overview as usize);
+                    has_overview = !entry.body.is_empty();
+                    entries = entry.children;
+                }
+            }
+        }
+    }
+    if let Some(extra) = segments.next() {
+        return Err(Error::Route(format!("unexpected segment `{extra}`")));
+    }
+    Ok(path)
+}
+
+fn find<'a, T>(
+    items: impl Iterator<Item = &'a T>,
+    segment: &str,
+    pred: impl Fn(&T) -> bool,
+) -> Result<(usize, &'a T), Error> {
+    items
+        .enumerate()
+        .find(|(_, item)| pred(item))
+        .ok_or_else(|| Error::Route(format!("nothing found for `{segment}`")))
+}
+
+fn find_project<'a>(
+    projects: &'a [Project<'a>],
+    segment: &str,
+) -> Result<(usize, &'a Project<'a>), Error> {
+    find(projects.iter(), segment, |p| slug(p.name) == slug(segment))
+}
+
+/// Lowercase the value and replace any runs of non-alphanumeric characters
+/// with a single `-`
+pub fn slug(value: &str) -> String {
+    let mut ret = String::with_capacity(value.len());
+    for ch in value.chars() {
+        if ch.is_alphanumeric() {
+            ret.extend(ch.to_lowercase());
+        } else if !ret.is_empty() && !ret.ends_with('-') {
+            ret.push('-');
+        }
+    }
+    if ret.ends_with('-') {
+        ret.pop();
+    }
+    ret
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Action, App, Event, DATABASE};
+
+    const PAGES: &[&str] = &["work", "oss", "edu", "talks"];
+
+    #[test]
+    fn slugs() {
+        assert_eq!(slug("Solution Design Group"), "solution-design-group");
+        assert_eq!(slug("  Rusty ECMA!"), "rusty-ecma");
+    }
+
+    #[test]
+    fn resolve_routes() {
+        assert_eq!(resolve(&DATABASE, PAGES, "").unwrap(), []);
+        assert_eq!(resolve(&DATABASE, PAGES, "#edu").unwrap(), [2]);
+        assert_eq!(
+            resolve(&DATABASE, PAGES, "work/SmartThings").unwrap(),
+            [0, 0]
+        );
+        assert!(resolve(&DATABASE, PAGES, "work/nowhere").is_err());
+        assert!(resolve(&DATABASE, PAGES, "edu/extra").is_err());
+        assert_eq!(resolve(&DATABASE, PAGES, "open-source").unwrap(), [1]);
+        // pages are matched by their whole id
+        assert_eq!(
+            resolve(&DATABASE, &["Work-History", "work"], "work").unwrap(),
+            [1]
+        );
+        assert_eq!(resolve(&DATABASE, PAGES, "talks").unwrap(), [3]);
+    }
+
+    #[test]
+    fn open_routes() {
+        let mut app = App::new();
+        app.open("oss/cosock/lustre").unwrap();
+        app.event(Event::Yank).unwrap();
+        let lustre = DATABASE.open_source[1]
+            .sub_projects
+            .iter()
+            .find(|p| p.name == "Lustre")
+            .unwrap();
+        assert_eq!(
+            app.take_action(),
+            Some(Action::Copy(lustre.long_desc.to_string()))
+        );
+    }
+
+    #[test]
+    fn open_unknown_route_falls_back_to_home() {
+        let mut app = App::new();
+        app.open("work/smartthings").unwrap();
+        app.open_or_home("work/nowhere");
+        assert!(app.sub_page.is_none());
+        assert_eq!(app.main_menu_state.selected(), Some(0));
+        assert_eq!(
+            app.status.as_deref(),
+            Some("Invalid route: nothing found for `nowhere`")
+        );
+    }
+}
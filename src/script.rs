@@ -0,0 +1,343 @@
+//! Scripted sequences of [`Event`]s used to drive the [`App`](crate::App)
+//! without a human at the keyboard.
+//!
+//! A script is a plain text file with one step per line in the form
+//! `<delay in ms> [event]`, the event is sent after waiting for the delay.
+//! Leaving off the event just waits, empty lines and lines starting with `#`
+//! are ignored.
+//!
+//! ```text
+//! # open the work page
+//! 1000 down
+//! 500 right
+//! 2000
+//! ```
+//!
+//! A script that visits every page of an [`App`] can also be generated with
+//! [`tour`].
+
+use std::time::Duration;
+
+use crate::{
+    data::{Entry, Project, Workplace},
+    App, Error, Event,
+};
+
+/// The script used by the `demo` command when no script file is provided
+pub const DEFAULT_SCRIPT: &str = "\
+1500
+750 down
+750 right
+1000 right
+1000 right
+750 down
+750 down
+750 down
+1000 left
+750 down
+1000 right
+1000 left
+1000 left
+1000 left
+750 down
+1000 right
+1000 right
+750 down
+750 down
+1000 left
+1000 left
+750 down
+1000 right
+2000 left
+750 up
+750 up
+750 up
+2000
+";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    pub delay: Duration,
+    pub event: Option<Event>,
+}
+
+pub fn parse(script: &str) -> Result<Vec<Step>, Error> {
+    let mut ret = Vec::new();
+    for (idx, line) in script.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut parts = line.split_whitespace();
+        let delay = parts
+            .next()
+            .and_then(|ms| ms.parse().ok())
+            .map(Duration::from_millis)
+            .ok_or_else(|| Error::Script {
+                line: idx + 1,
+                msg: "expected a delay in milliseconds".to_string(),
+            })?;
+        let event = parts
+            .next()
+            .map(|ev| {
+                ev.parse().map_err(|e: Error| Error::Script {
+                    line: idx + 1,
+                    msg: e.to_string(),
+                })
+            })
+            .transpose()?;
+        if parts.next().is_some() {
+            return Err(Error::Script {
+                line: idx + 1,
+                msg: "unexpected trailing content".to_string(),
+            });
+        }
+        ret.push(Step { delay, event });
+    }
+    Ok(ret)
+}
+
+/// The most lines a detail view will be scrolled during a [`tour`]
+const MAX_TOUR_SCROLL: usize = 10;
+
+/// Generate a script that visits every page in the app's main menu, opening
+/// each job detail, (sub-)project, publication, talk and section entry along
+/// the way and waiting `dwell` after each step. Pages added with
+/// [`App::push_page`] or [`App::insert_page`] are opened and closed. The
+/// script starts and ends on the main menu's Home entry.
+pub fn tour(app: &App, dwell: Duration) -> Vec<Step> {
+    let db = app.db;
+    let mut tour = Tour {
+        steps: vec![Step {
+            delay: dwell,
+            event: None,
+        }],
+        dwell,
+    };
+    for page in &app.pages {
+        tour.push(Event::Down);
+        tour.push(Event::Right);
+        match page.id() {
+            "work" => tour.jobs(db.jobs),
+            "oss" => {
+                for (idx, project) in db.open_source.iter().enumerate() {
+                    if idx > 0 {
+                        tour.push(Event::Down);
+                    }
+                    tour.project(project);
+                }
+            }
+            "publications" => tour.citations(db.publications.iter().map(|p| p.summary)),
+            "talks" => tour.citations(db.talks.iter().map(|t| t.summary)),
+            id => {
+                if let Some(section) = db.sections.iter().find(|s| s.title == id) {
+                    tour.entries(section.entries, false);
+                }
+            }
+        }
+        tour.push(Event::Left);
+    }
+    for _ in 0..app.pages.len() {
+        tour.push(Event::Up);
+    }
+    tour.steps
+}
+
+struct Tour {
+    steps: Vec<Step>,
+    dwell: Duration,
+}
+
+impl Tour {
+    fn push(&mut self, event: Event) {
+        self.steps.push(Step {
+            delay: self.dwell,
+            event: Some(event),
+        });
+    }
+
+    /// Scroll through a detail view a line at a time, a little quicker than
+    /// the normal dwell time
+    fn scroll(&mut self, content: &str) {
+        let lines = content.lines().count().min(MAX_TOUR_SCROLL);
+        for _ in 0..lines {
+            self.steps.push(Step {
+                delay: self.dwell / 4,
+                event: Some(Event::Down),
+            });
+        }
+    }
+
+    /// Open each job and each of its details
+    fn jobs(&mut self, jobs: &[Workplace]) {
+        for (idx, job) in jobs.iter().enumerate() {
+            if idx > 0 {
+                self.push(Event::Down);
+            }
+            self.push(Event::Right);
+            for (idx, detail) in job.all_details().enumerate() {
+                if idx > 0 {
+                    self.push(Event::Down);
+                }
+                self.push(Event::Right);
+                self.scroll(detail.detail);
+                self.push(Event::Left);
+            }
+            self.push(Event::Left);
+        }
+    }
+
+    /// Open each publication or talk, scrolling through its abstract
+    fn citations<'a>(&mut self, summaries: impl Iterator<Item = &'a str>) {
+        for (idx, summary) in summaries.enumerate() {
+            if idx > 0 {
+                self.push(Event::Down);
+            }
+            self.push(Event::Right);
+            self.scroll(summary);
+            self.push(Event::Left);
+        }
+    }
+
+    /// Open a project, visiting its description and each of its sub-projects
+    /// before returning to the previous list
+    fn project(&mut self, project: &Project) {
+        self.push(Event::Right);
+        if project.sub_projects.is_empty() {
+            self.scroll(project.long_desc);
+            self.push(Event::Left);
+            return;
+        }
+        self.push(Event::Right);
+        self.scroll(project.long_desc);
+        self.push(Event::Left);
+        for sub_project in project.sub_projects {
+            self.push(Event::Down);
+            self.project(sub_project);
+        }
+        self.push(Event::Left);
+    }
+
+    /// Open each entry in a section, visiting the children of any entry that
+    /// has them. `has_overview` indicates the list starts with the parent
+    /// entry's body.
+    fn entries(&mut self, entries: &[Entry], has_overview: bool) {
+        if has_overview {
+            self.push(Event::Right);
+            self.push(Event::Left);
+            self.push(Event::Down);
+        }
+        for (idx, entry) in entries.iter().enumerate() {
+            if idx > 0 {
+                self.push(Event::Down);
+            }
+            self.push(Event::Right);
+            if entry.children.is_empty() {
+                self.scroll(entry.body);
+            } else {
+                self.entries(entry.children, !entry.body.is_empty());
+            }
+            self.push(Event::Left);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use ratatui::{buffer::Buffer, layout::Rect};
+
+    use super::*;
+    use crate::{Navigable, Page};
+
+    /// A page that counts how many times it was opened
+    #[derive(Clone)]
+    struct Visits(Arc<AtomicUsize>);
+
+    impl Navigable for Visits {
+        fn increment_selection(&mut self) {}
+        fn decrement_selection(&mut self) {}
+        fn handle_enter(&mut self) {}
+        fn handle_left(&mut self) -> bool {
+            false
+        }
+    }
+
+    impl Page for Visits {
+        fn title(&self) -> &str {
+            "Visits"
+        }
+
+        fn render(&mut self, _area: Rect, _buf: &mut Buffer) {}
+
+        fn reset(&mut self) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn parse_script() {
+        let script = "\
+# a comment
+
+1500
+750 down
+  250   right
+";
+        let steps = parse(script).unwrap();
+        assert_eq!(
+            steps,
+            [
+                Step {
+                    delay: Duration::from_millis(1500),
+                    event: None,
+                },
+                Step {
+                    delay: Duration::from_millis(750),
+                    event: Some(Event::Down),
+                },
+                Step {
+                    delay: Duration::from_millis(250),
+                    event: Some(Event::Right),
+                },
+            ]
+        );
+        assert!(parse(DEFAULT_SCRIPT).is_ok());
+    }
+
+    #[test]
+    fn parse_script_errors() {
+        for (script, expected_line) in [
+            ("down", 1),
+            ("100\n-5 down", 2),
+            ("100 sideways", 1),
+            ("# comment\n\n100 down up", 3),
+        ] {
+            let err = parse(script).unwrap_err();
+            assert!(
+                matches!(err, Error::Script { line, .. } if line == expected_line),
+                "{script:?}: {err}"
+            );
+        }
+    }
+
+    #[test]
+    fn tour_visits_added_pages() {
+        let visits = Arc::new(AtomicUsize::new(0));
+        let mut app = App::new();
+        app.insert_page(1, Visits(visits.clone()));
+        app.push_page(Visits(visits.clone()));
+        for step in tour(&app, Duration::ZERO) {
+            if let Some(event) = step.event {
+                app.event(event).unwrap();
+            }
+        }
+        assert_eq!(visits.load(Ordering::Relaxed), 2);
+        assert_eq!(app.main_menu_state.selected(), Some(0));
+        assert!(app.sub_page.is_none());
+    }
+}
@@ -0,0 +1,307 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    widgets::{List, ListItem, StatefulWidget, Widget},
+};
+
+use crate::{
+    data::{Entry, Section},
+    detail_view::DetailView,
+    list_state::ListStateWrapper as ListState,
+    work::{date_range, headline_list_item},
+    Action, Context, Navigable, Page, DEFAULT_STYLE,
+};
+
+/// A list of entries from `sections.toml`, entries without children open a
+/// [`DetailView`] of their body while entries with children open another
+/// list with the entry's own body as the first item.
+#[derive(Debug, Clone)]
+pub struct SectionView<'a> {
+    title: &'a str,
+    overview: Option<&'a Entry<'a>>,
+    entries: &'a [Entry<'a>],
+    menu: ListState,
+    sub_page: Option<Box<EntryPage<'a>>>,
+    context: Context,
+}
+
+#[derive(Debug, Clone)]
+enum EntryPage<'a> {
+    Detail(DetailView<'a>),
+    List(SectionView<'a>),
+}
+
+impl<'a> SectionView<'a> {
+    pub fn new(section: &'a Section<'a>, context: Context) -> Self {
+        Self::with_entries(section.title, None, section.entries, context)
+    }
+
+    fn with_entries(
+        title: &'a str,
+        overview: Option<&'a Entry<'a>>,
+        entries: &'a [Entry<'a>],
+        context: Context,
+    ) -> Self {
+        let len = entries.len() + overview.is_some() as usize;
+        Self {
+            title,
+            overview,
+            entries,
+            menu: ListState::new(len.saturating_sub(1)),
+            sub_page: None,
+            context,
+        }
+    }
+
+    fn selected_entry(&self) -> Option<EntryPage<'a>> {
+        let mut idx = self.menu.selected()?;
+        if let Some(overview) = self.overview {
+            if idx == 0 {
+                return Some(EntryPage::Detail(DetailView::new(
+                    overview.headline,
+                    overview.body,
+                    self.context.clone(),
+                )));
+            }
+            idx -= 1;
+        }
+        let entry = self.entries.get(idx)?;
+        if entry.children.is_empty() {
+            return Some(EntryPage::Detail(DetailView::new(
+                entry.headline,
+                entry.body,
+                self.context.clone(),
+            )));
+        }
+        let overview = (!entry.body.is_empty()).then_some(entry);
+        Some(EntryPage::List(Self::with_entries(
+            entry.headline,
+            overview,
+            entry.children,
+            self.context.clone(),
+        )))
+    }
+}
+
+impl<'a> Widget for SectionView<'a> {
+    fn render(mut self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        if let Some(sub_page) = self.sub_page {
+            match *sub_page {
+                EntryPage::Detail(inner) => inner.render(area, buf),
+                EntryPage::List(inner) => inner.render(area, buf),
+            }
+            return;
+        }
+        let overview = self
+            .overview
+            .map(|e| headline_list_item("Overview", e.snippet, None));
+        let list_items: Vec<ListItem> = overview
+            .into_iter()
+            .chain(self.entries.iter().map(|e| {
+                let dates = e.start.map(|start| date_range(start, e.end));
+                headline_list_item(e.headline, e.snippet, dates)
+            }))
+            .collect();
+        let list = List::new(list_items)
+            .highlight_style(Style::new().bg(Color::Green).fg(Color::Black))
+            .style(DEFAULT_STYLE);
+        StatefulWidget::render(list, area, buf, self.menu.as_mut());
+    }
+}
+
+impl<'a> Navigable for SectionView<'a> {
+    fn increment_selection(&mut self) {
+        match self.sub_page.as_deref_mut() {
+            Some(EntryPage::Detail(inner)) => inner.increment_selection(),
+            Some(EntryPage::List(inner)) => inner.increment_selection(),
+            None => self.menu.increment(),
+        }
+    }
+
+    fn decrement_selection(&mut self) {
+        match self.sub_page.as_deref_mut() {
+            Some(EntryPage::Detail(inner)) => inner.decrement_selection(),
+            Some(EntryPage::List(inner)) => inner.decrement_selection(),
+            None => self.menu.decrement(),
+        }
+    }
+
+    fn select_item(&mut self, idx: usize) {
+        match self.sub_page.as_deref_mut() {
+            Some(EntryPage::Detail(inner)) => inner.select_item(idx),
+            Some(EntryPage::List(inner)) => inner.select_item(idx),
+            None => self.menu.select_item(idx),
+        }
+    }
+
+    fn handle_enter(&mut self) {
+        match self.sub_page.as_deref_mut() {
+            Some(EntryPage::Detail(inner)) => inner.handle_enter(),
+            Some(EntryPage::List(inner)) => inner.handle_enter(),
+            None => self.sub_page = self.selected_entry().map(Box::new),
+        }
+    }
+
+    fn handle_left(&mut self) -> bool {
+        let Some(mut sub_page) = self.sub_page.take() else {
+            return false;
+        };
+        let keep = match sub_page.as_mut() {
+            EntryPage::Detail(inner) => inner.handle_left(),
+            EntryPage::List(inner) => inner.handle_left(),
+        };
+        if keep {
+            self.sub_page = Some(sub_page);
+        }
+        true
+    }
+
+    fn focus_next_link(&mut self) {
+        match self.sub_page.as_deref_mut() {
+            Some(EntryPage::Detail(inner)) => inner.focus_next_link(),
+            Some(EntryPage::List(inner)) => inner.focus_next_link(),
+            None => {}
+        }
+    }
+
+    fn jump_to_heading(&mut self, forward: bool) {
+        match self.sub_page.as_deref_mut() {
+            Some(EntryPage::Detail(inner)) => inner.jump_to_heading(forward),
+            Some(EntryPage::List(inner)) => inner.jump_to_heading(forward),
+            None => {}
+        }
+    }
+
+    fn fit_to_view(&mut self) {
+        match self.sub_page.as_deref_mut() {
+            Some(EntryPage::Detail(inner)) => inner.fit_to_view(),
+            Some(EntryPage::List(inner)) => inner.fit_to_view(),
+            None => {}
+        }
+    }
+
+    fn take_action(&mut self) -> Option<Action> {
+        match self.sub_page.as_deref_mut()? {
+            EntryPage::Detail(inner) => inner.take_action(),
+            EntryPage::List(inner) => inner.take_action(),
+        }
+    }
+
+    fn yank(&self) -> Option<String> {
+        match self.sub_page.as_deref() {
+            Some(EntryPage::Detail(inner)) => inner.yank(),
+            Some(EntryPage::List(inner)) => inner.yank(),
+            None => {
+                let mut idx = self.menu.selected()?;
+                if let Some(overview) = self.overview {
+                    if idx == 0 {
+                        return Some(overview.body.to_string());
+                    }
+                    idx -= 1;
+                }
+                Some(self.entries.get(idx)?.body.to_string())
+            }
+        }
+    }
+}
+
+impl<'a> Page for SectionView<'a> {
+    fn title(&self) -> &str {
+        self.title
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        self.fit_to_view();
+        Widget::render(self.clone(), area, buf);
+    }
+
+    fn reset(&mut self) {
+        *self = Self::with_entries(
+            self.title,
+            self.overview,
+            self.entries,
+            self.context.clone(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ratatui::{backend::TestBackend, Terminal};
+
+    use super::*;
+    use crate::date::Date;
+
+    const fn entry(headline: &'static str, children: &'static [Entry<'static>]) -> Entry<'static> {
+        Entry {
+            headline,
+            snippet: "snippet",
+            body: headline,
+            start: None,
+            end: None,
+            children,
+        }
+    }
+
+    static SECTION: Section = Section {
+        title: "Volunteering",
+        entries: &[
+            Entry {
+                start: Some(Date::new(2021, 3)),
+                ..entry("Mentor", &[])
+            },
+            Entry {
+                start: Some(Date::year(2018)),
+                end: Some(Date::new(2020, 6)),
+                ..entry(
+                    "Meetup",
+                    &[entry("First Talk", &[]), entry("Second Talk", &[])],
+                )
+            },
+            Entry {
+                body: "",
+                ..entry("Workshops", &[entry("Rust", &[])])
+            },
+        ],
+    };
+
+    #[test]
+    fn render_entries() {
+        let view = SectionView::new(&SECTION, Context::default());
+        let mut term = Terminal::new(TestBackend::new(40, 8)).unwrap();
+        term.draw(|f| f.render_widget(view.clone(), f.area()))
+            .unwrap();
+        insta::assert_snapshot!(term.backend());
+    }
+
+    #[test]
+    fn open_entries_with_children() {
+        let mut view = SectionView::new(&SECTION, Context::default());
+        view.increment_selection();
+        view.handle_enter();
+        // the entry's own body is listed first as an overview
+        let mut selected = vec![view.yank()];
+        for _ in 0..2 {
+            view.increment_selection();
+            selected.push(view.yank());
+        }
+        let expected = ["Meetup", "First Talk", "Second Talk"];
+        assert_eq!(selected, expected.map(|s| Some(s.to_string())));
+        view.handle_enter();
+        assert!(matches!(
+            view.sub_page.as_deref(),
+            Some(EntryPage::List(list)) if list.sub_page.is_some()
+        ));
+        assert!(view.handle_left());
+        assert!(view.handle_left());
+        assert!(!view.handle_left());
+        // without a body there is no overview
+        view.increment_selection();
+        view.handle_enter();
+        assert_eq!(view.yank().as_deref(), Some("Rust"));
+    }
+}
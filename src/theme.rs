@@ -0,0 +1,44 @@
+//! The colors used for syntax highlighting code blocks and accenting
+//! headings in markdown.
+//!
+//! A theme is provided when the [`App`](crate::App) is created, see
+//! [`App::with_theme`](crate::App::with_theme), and shared with its pages
+//! through the [`Context`](crate::Context).
+
+use ratatui::style::Color;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// The text of a code block that isn't highlighted
+    pub code: Color,
+    /// The background of a code block
+    pub code_background: Color,
+    pub keyword: Color,
+    pub string: Color,
+    pub comment: Color,
+    pub number: Color,
+    /// The name of a function where it is called or defined
+    pub function: Color,
+    /// The accent used to set apart smaller markdown headings
+    pub heading: Color,
+}
+
+impl Theme {
+    /// Green on black to match the rest of the app
+    pub const DEFAULT: Self = Self {
+        code: Color::Green,
+        code_background: Color::Indexed(234),
+        keyword: Color::Magenta,
+        string: Color::Yellow,
+        comment: Color::DarkGray,
+        number: Color::LightRed,
+        function: Color::Cyan,
+        heading: Color::LightGreen,
+    };
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
@@ -0,0 +1,526 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style},
+    symbols,
+    text::{Line, Span},
+    widgets::Widget,
+};
+
+use crate::{
+    data::{today, Database, Project, Workplace},
+    date::Date,
+    list_state::ListStateWrapper as ListState,
+    oss::ProjectView,
+    work::{date_range, JobView},
+    Action, Context, Navigable, Page, DEFAULT_STYLE,
+};
+
+/// A Gantt style chart of every job, school and project with a date, one row
+/// per entry ordered by when it started. Selecting a job or project opens it.
+#[derive(Debug, Clone)]
+pub struct TimelineView<'a> {
+    bars: Vec<Bar<'a>>,
+    today: Date,
+    menu: ListState,
+    sub_page: Option<TimelineEntry<'a>>,
+    context: Context,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Bar<'a> {
+    label: &'a str,
+    kind: Kind<'a>,
+    start: Date,
+    /// `None` when the entry is ongoing
+    end: Option<Date>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Kind<'a> {
+    Job(&'a Workplace<'a>),
+    School,
+    Project(&'a Project<'a>),
+}
+
+const JOB_COLOR: Color = Color::Green;
+const SCHOOL_COLOR: Color = Color::Yellow;
+const PROJECT_COLOR: Color = Color::Cyan;
+
+impl<'a> Kind<'a> {
+    fn color(&self) -> Color {
+        match self {
+            Self::Job(_) => JOB_COLOR,
+            Self::School => SCHOOL_COLOR,
+            Self::Project(_) => PROJECT_COLOR,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum TimelineEntry<'a> {
+    Job(JobView<'a>),
+    Project(ProjectView<'a>),
+}
+
+/// Every dated entry in the database sorted by start date. Schools with only
+/// a graduation date are displayed as a single point.
+pub(crate) fn bars<'a>(db: &Database<'a>) -> Vec<Bar<'a>> {
+    let jobs = db.jobs.iter().map(|job| Bar {
+        label: job.name,
+        kind: Kind::Job(job),
+        start: job.start,
+        end: job.end,
+    });
+    let schools = db.education.iter().filter_map(|school| {
+        let start = school.start.or(school.graduated)?;
+        Some(Bar {
+            label: school.name,
+            kind: Kind::School,
+            start,
+            end: school.graduated,
+        })
+    });
+    let projects = db.open_source.iter().filter_map(|project| {
+        Some(Bar {
+            label: project.name,
+            kind: Kind::Project(project),
+            start: project.start?,
+            end: project.end,
+        })
+    });
+    let mut ret: Vec<_> = jobs.chain(schools).chain(projects).collect();
+    ret.sort_by_key(|bar| bar.start);
+    ret
+}
+
+impl<'a> TimelineView<'a> {
+    pub fn new(db: &Database<'a>, context: Context) -> Self {
+        let bars = bars(db);
+        Self {
+            menu: ListState::new(bars.len().saturating_sub(1)),
+            bars,
+            today: today(),
+            sub_page: None,
+            context,
+        }
+    }
+
+    /// The first and last month displayed in the chart
+    fn range(&self) -> (i32, i32) {
+        let first = self
+            .bars
+            .iter()
+            .map(|bar| bar.start.months())
+            .min()
+            .unwrap_or_else(|| self.today.months());
+        let last = self
+            .bars
+            .iter()
+            .filter_map(|bar| bar.end)
+            .map(|end| end.months())
+            .chain(Some(self.today.months()))
+            .max()
+            .unwrap_or(first);
+        (first, last.max(first + 1))
+    }
+}
+
+impl<'a> Widget for TimelineView<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        if let Some(sub_page) = self.sub_page {
+            match sub_page {
+                TimelineEntry::Job(inner) => inner.render(area, buf),
+                TimelineEntry::Project(inner) => inner.render(area, buf),
+            }
+            return;
+        }
+        let [chart, axis, legend] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(2),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+        let longest = self
+            .bars
+            .iter()
+            .map(|bar| Span::raw(bar.label).width() as u16 + 1)
+            .max()
+            .unwrap_or_default();
+        let [labels, bars] = Layout::horizontal([
+            Constraint::Length(longest.min(area.width / 3)),
+            Constraint::Fill(1),
+        ])
+        .areas(chart);
+        // too small to fit the chart and its axis
+        if bars.width == 0 || area.height < 3 {
+            return;
+        }
+        let (first, last) = self.range();
+        let width = bars.width.saturating_sub(1) as i32;
+        // the offset from the left of the chart for a date
+        let column = |date: Date| (date.months() - first) * width / (last - first);
+
+        let selected = self.menu.selected().unwrap_or_default();
+        // keep the selected row on screen
+        let offset = (selected + 1).saturating_sub(chart.height as usize);
+        let rows = self.bars.iter().enumerate().skip(offset);
+        for ((idx, bar), y) in rows.zip(chart.top()..chart.bottom()) {
+            let label_style = if idx == selected {
+                Style::new().bg(Color::Green).fg(Color::Black)
+            } else {
+                DEFAULT_STYLE
+            };
+            buf.set_stringn(labels.x, y, bar.label, labels.width as usize, label_style);
+            let style = Style::new().fg(bar.kind.color());
+            let start = bars.x + column(bar.start).max(0) as u16;
+            let end = bars.x + column(bar.end.unwrap_or(self.today)).max(0) as u16;
+            for x in start..=end {
+                if let Some(cell) = buf.cell_mut((x, y)) {
+                    cell.set_symbol(symbols::block::FULL).set_style(style);
+                }
+            }
+            if let Some(cell) = buf.cell_mut((end, y)).filter(|_| bar.end.is_none()) {
+                cell.set_symbol("▶").set_style(style);
+            }
+        }
+
+        // a line below the chart with a tick and label for each year
+        for x in bars.left()..bars.right() {
+            if let Some(cell) = buf.cell_mut((x, axis.y)) {
+                cell.set_symbol(symbols::line::HORIZONTAL)
+                    .set_style(DEFAULT_STYLE);
+            }
+        }
+        let mut free = bars.x;
+        for year in (first / 12)..=(last / 12) {
+            let x = column(Date::year(year as u16));
+            if x < 0 || x >= bars.width as i32 || bars.x + (x as u16) < free {
+                continue;
+            }
+            let x = bars.x + x as u16;
+            if let Some(cell) = buf.cell_mut((x, axis.y)) {
+                cell.set_symbol(symbols::line::HORIZONTAL_DOWN);
+            }
+            let label = year.to_string();
+            buf.set_stringn(
+                x,
+                axis.y + 1,
+                &label,
+                (bars.right() - x) as usize,
+                DEFAULT_STYLE,
+            );
+            free = x + label.len() as u16 + 1;
+        }
+        Line::from(vec![
+            Span::styled(symbols::block::FULL, Style::new().fg(SCHOOL_COLOR)),
+            " School  ".into(),
+            Span::styled(symbols::block::FULL, Style::new().fg(JOB_COLOR)),
+            " Work  ".into(),
+            Span::styled(symbols::block::FULL, Style::new().fg(PROJECT_COLOR)),
+            " Open Source  ".into(),
+            "▶ Current".into(),
+        ])
+        .style(DEFAULT_STYLE)
+        .centered()
+        .render(legend, buf);
+    }
+}
+
+impl<'a> Navigable for TimelineEntry<'a> {
+    fn increment_selection(&mut self) {
+        match self {
+            Self::Job(inner) => inner.increment_selection(),
+            Self::Project(inner) => inner.increment_selection(),
+        }
+    }
+
+    fn decrement_selection(&mut self) {
+        match self {
+            Self::Job(inner) => inner.decrement_selection(),
+            Self::Project(inner) => inner.decrement_selection(),
+        }
+    }
+
+    fn select_item(&mut self, idx: usize) {
+        match self {
+            Self::Job(inner) => inner.select_item(idx),
+            Self::Project(inner) => inner.select_item(idx),
+        }
+    }
+
+    fn handle_enter(&mut self) {
+        match self {
+            Self::Job(inner) => inner.handle_enter(),
+            Self::Project(inner) => inner.handle_enter(),
+        }
+    }
+
+    fn handle_left(&mut self) -> bool {
+        match self {
+            Self::Job(inner) => inner.handle_left(),
+            Self::Project(inner) => inner.handle_left(),
+        }
+    }
+
+    fn focus_next_link(&mut self) {
+        match self {
+            Self::Job(inner) => inner.focus_next_link(),
+            Self::Project(inner) => inner.focus_next_link(),
+        }
+    }
+
+    fn jump_to_heading(&mut self, forward: bool) {
+        match self {
+            Self::Job(inner) => inner.jump_to_heading(forward),
+            Self::Project(inner) => inner.jump_to_heading(forward),
+        }
+    }
+
+    fn fit_to_view(&mut self) {
+        match self {
+            Self::Job(inner) => inner.fit_to_view(),
+            Self::Project(inner) => inner.fit_to_view(),
+        }
+    }
+
+    fn take_action(&mut self) -> Option<Action> {
+        match self {
+            Self::Job(inner) => inner.take_action(),
+            Self::Project(inner) => inner.take_action(),
+        }
+    }
+
+    fn yank(&self) -> Option<String> {
+        match self {
+            Self::Job(inner) => inner.yank(),
+            Self::Project(inner) => inner.yank(),
+        }
+    }
+}
+
+impl<'a> Navigable for TimelineView<'a> {
+    fn increment_selection(&mut self) {
+        if let Some(sub_page) = self.sub_page.as_mut() {
+            sub_page.increment_selection();
+            return;
+        }
+        self.menu.increment();
+    }
+
+    fn decrement_selection(&mut self) {
+        if let Some(sub_page) = self.sub_page.as_mut() {
+            sub_page.decrement_selection();
+            return;
+        }
+        self.menu.decrement();
+    }
+
+    fn select_item(&mut self, idx: usize) {
+        if let Some(sub_page) = self.sub_page.as_mut() {
+            sub_page.select_item(idx);
+            return;
+        }
+        self.menu.select_item(idx);
+    }
+
+    fn handle_enter(&mut self) {
+        if let Some(sub_page) = self.sub_page.as_mut() {
+            sub_page.handle_enter();
+            return;
+        }
+        let Some(bar) = self.menu.selected().and_then(|idx| self.bars.get(idx)) else {
+            return;
+        };
+        self.sub_page = match bar.kind {
+            Kind::Job(job) => Some(TimelineEntry::Job(JobView::new(
+                job.clone(),
+                self.context.clone(),
+            ))),
+            Kind::Project(project) => Some(TimelineEntry::Project(ProjectView::new(
+                project,
+                self.context.clone(),
+            ))),
+            Kind::School => None,
+        };
+    }
+
+    fn handle_left(&mut self) -> bool {
+        if let Some(mut sub_page) = self.sub_page.take() {
+            if sub_page.handle_left() {
+                self.sub_page = Some(sub_page);
+            }
+            return true;
+        }
+        false
+    }
+
+    fn focus_next_link(&mut self) {
+        if let Some(sub_page) = self.sub_page.as_mut() {
+            sub_page.focus_next_link();
+        }
+    }
+
+    fn jump_to_heading(&mut self, forward: bool) {
+        if let Some(sub_page) = self.sub_page.as_mut() {
+            sub_page.jump_to_heading(forward);
+        }
+    }
+
+    fn fit_to_view(&mut self) {
+        if let Some(sub_page) = self.sub_page.as_mut() {
+            sub_page.fit_to_view();
+        }
+    }
+
+    fn take_action(&mut self) -> Option<Action> {
+        self.sub_page.as_mut()?.take_action()
+    }
+
+    fn yank(&self) -> Option<String> {
+        if let Some(sub_page) = self.sub_page.as_ref() {
+            return sub_page.yank();
+        }
+        let bar = self.bars.get(self.menu.selected()?)?;
+        Some(format!("{}, {}", bar.label, date_range(bar.start, bar.end)))
+    }
+}
+
+impl<'a> Page for TimelineView<'a> {
+    fn title(&self) -> &str {
+        "Timeline"
+    }
+
+    fn id(&self) -> &str {
+        "timeline"
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        self.fit_to_view();
+        Widget::render(self.clone(), area, buf);
+    }
+
+    fn reset(&mut self) {
+        self.menu = ListState::new(self.bars.len().saturating_sub(1));
+        self.sub_page = None;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ratatui::{backend::TestBackend, Terminal};
+
+    use super::*;
+    use crate::{
+        data::{Contact, School},
+        DATABASE,
+    };
+
+    static JOBS: &[Workplace] = &[
+        Workplace {
+            name: "Acme",
+            title: "Engineer",
+            start: Date::new(2019, 4),
+            end: Some(Date::new(2021, 8)),
+            details: &[],
+            roles: &[],
+        },
+        Workplace {
+            name: "Globex",
+            title: "Lead",
+            start: Date::new(2021, 9),
+            end: None,
+            details: &[],
+            roles: &[],
+        },
+    ];
+
+    static SCHOOLS: &[School] = &[School {
+        name: "State U",
+        start: Some(Date::new(2015, 9)),
+        graduated: Some(Date::new(2019, 5)),
+        desc: "",
+    }];
+
+    static PROJECTS: &[Project] = &[Project {
+        name: "widgets",
+        short_desc: "A widget library",
+        long_desc: "",
+        start: Some(Date::new(2020, 2)),
+        end: Some(Date::new(2023, 1)),
+        sub_projects: &[],
+    }];
+
+    static TIMELINE: Database = Database {
+        name: "Jane Doe",
+        tag_line: "",
+        contact: Contact {
+            email: None,
+            website: None,
+            phone: None,
+            location: None,
+            profiles: &[],
+        },
+        jobs: JOBS,
+        open_source: PROJECTS,
+        education: SCHOOLS,
+        publications: &[],
+        talks: &[],
+        certifications: &[],
+        sections: &[],
+    };
+
+    fn view() -> TimelineView<'static> {
+        let mut view = TimelineView::new(&TIMELINE, Context::default());
+        view.today = Date::new(2024, 6);
+        view
+    }
+
+    #[test]
+    fn render_timeline() {
+        let mut view = view();
+        view.increment_selection();
+        let mut term = Terminal::new(TestBackend::new(60, 12)).unwrap();
+        term.draw(|f| f.render_widget(view.clone(), f.area()))
+            .unwrap();
+        insta::assert_snapshot!(term.backend());
+    }
+
+    #[test]
+    fn open_bars() {
+        let mut view = view();
+        let labels: Vec<_> = view.bars.iter().map(|bar| bar.label).collect();
+        assert_eq!(labels, ["State U", "Acme", "widgets", "Globex"]);
+        view.select_item(0);
+        view.handle_enter();
+        assert!(view.sub_page.is_none());
+        view.select_item(1);
+        view.handle_enter();
+        assert!(matches!(view.sub_page, Some(TimelineEntry::Job(_))));
+        assert!(view.handle_left());
+        assert!(view.sub_page.is_none());
+        view.select_item(2);
+        view.handle_enter();
+        assert!(matches!(view.sub_page, Some(TimelineEntry::Project(_))));
+        assert!(view.handle_left());
+        view.select_item(3);
+        view.handle_enter();
+        assert!(matches!(view.sub_page, Some(TimelineEntry::Job(_))));
+    }
+
+    #[test]
+    fn render_tiny_areas() {
+        let view = TimelineView::new(&DATABASE, Context::default());
+        let sizes = (1..=11)
+            .flat_map(|width| (0..=1).map(move |height| (width, height)))
+            .chain((4..=5).map(|height| (0, height)));
+        for (width, height) in sizes {
+            let area = Rect::new(0, 0, width, height);
+            let mut buf = Buffer::empty(area);
+            view.clone().render(area, &mut buf);
+        }
+    }
+}
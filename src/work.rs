@@ -1,3 +1,5 @@
+use std::fmt::Display;
+
 use ratatui::{
     buffer::Buffer,
     layout::{Alignment, Constraint, Flex, Layout, Rect},
@@ -8,24 +10,31 @@ use ratatui::{
 };
 
 use crate::{
-    data::{source::DATABASE, Detail, Workplace},
+    data::{today, Detail, Workplace},
+    date::Date,
     detail_view::DetailView,
     list_state::ListStateWrapper as ListState,
     markdown::convert_md,
-    Navigable, DEFAULT_STYLE,
+    Action, Context, Navigable, Page, DEFAULT_STYLE,
 };
 
 #[derive(Debug, Clone)]
 pub struct WorkView<'a> {
+    jobs: &'a [Workplace<'a>],
+    today: Date,
     menu: ListState,
     work: Option<JobView<'a>>,
+    context: Context,
 }
 
-impl<'a> Default for WorkView<'a> {
-    fn default() -> Self {
+impl<'a> WorkView<'a> {
+    pub fn new(jobs: &'a [Workplace<'a>], context: Context) -> Self {
         Self {
-            menu: ListState::new(DATABASE.jobs.len().saturating_sub(1)),
+            jobs,
+            today: today(),
+            menu: ListState::new(jobs.len().saturating_sub(1)),
             work: None,
+            context,
         }
     }
 }
@@ -39,16 +48,16 @@ impl<'a> Widget for WorkView<'a> {
             sub_page.render(area, buf);
             return;
         }
-        let list_items: Vec<ListItem> = DATABASE
+        let list_items: Vec<ListItem> = self
             .jobs
             .iter()
             .map(|w| {
-                let dts = if let Some(end) = w.end.as_ref() {
-                    format!("{} - {end}", w.start)
-                } else {
-                    format!("{} - Current", w.start)
-                };
-                ListItem::new(Text::from(format!("{} - {}\n    {dts}", w.name, w.title,)))
+                let dts = date_range(w.start, w.end);
+                let tenure = tenure(w.tenure(self.today));
+                ListItem::new(Text::from(format!(
+                    "{} - {}\n    {dts} ({tenure})",
+                    w.name, w.title,
+                )))
             })
             .collect();
         let list = List::new(list_items)
@@ -75,6 +84,14 @@ impl<'a> Navigable for WorkView<'a> {
         self.menu.decrement();
     }
 
+    fn select_item(&mut self, idx: usize) {
+        if let Some(sub_page) = &mut self.work {
+            sub_page.select_item(idx);
+            return;
+        }
+        self.menu.select_item(idx);
+    }
+
     fn handle_enter(&mut self) {
         if let Some(sub_page) = &mut self.work {
             sub_page.handle_enter();
@@ -83,7 +100,10 @@ impl<'a> Navigable for WorkView<'a> {
         let Some(idx) = self.menu.selected() else {
             return;
         };
-        self.work = DATABASE.jobs.get(idx).cloned().map(Into::into);
+        self.work = self
+            .jobs
+            .get(idx)
+            .map(|job| JobView::new(job.clone(), self.context.clone()));
     }
 
     fn handle_left(&mut self) -> bool {
@@ -95,24 +115,79 @@ impl<'a> Navigable for WorkView<'a> {
         };
         false
     }
+
+    fn focus_next_link(&mut self) {
+        if let Some(sub_page) = &mut self.work {
+            sub_page.focus_next_link();
+        }
+    }
+
+    fn jump_to_heading(&mut self, forward: bool) {
+        if let Some(sub_page) = &mut self.work {
+            sub_page.jump_to_heading(forward);
+        }
+    }
+
+    fn fit_to_view(&mut self) {
+        if let Some(sub_page) = &mut self.work {
+            sub_page.fit_to_view();
+        }
+    }
+
+    fn take_action(&mut self) -> Option<Action> {
+        self.work.as_mut()?.take_action()
+    }
+
+    fn yank(&self) -> Option<String> {
+        self.work.as_ref()?.yank()
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct JobView<'a> {
-    workplace: Workplace,
+    workplace: Workplace<'a>,
+    today: Date,
     menu: ListState,
     detail: Option<DetailView<'a>>,
+    context: Context,
 }
 
-impl<'a> From<Workplace> for JobView<'a> {
-    fn from(value: Workplace) -> Self {
-        let menu = ListState::new(value.details.len().saturating_sub(1));
+impl<'a> JobView<'a> {
+    pub fn new(workplace: Workplace<'a>, context: Context) -> Self {
+        // each role's heading is a row in the list that can't be selected
+        let rows = workplace.all_details().count() + workplace.roles.len();
+        let menu = ListState::with_skipped(rows.saturating_sub(1), role_rows(&workplace));
         Self {
-            workplace: value,
+            workplace,
+            today: today(),
             menu,
             detail: None,
+            context,
         }
     }
+
+    fn selected_detail(&self) -> Option<&'a Detail<'a>> {
+        let row = self.menu.selected()?;
+        let headings = role_rows(&self.workplace)
+            .into_iter()
+            .take_while(|heading| *heading < row)
+            .count();
+        self.workplace.all_details().nth(row - headings)
+    }
+}
+
+/// The row of each role's heading in the list of a job's details
+fn role_rows(workplace: &Workplace) -> Vec<usize> {
+    let mut row = workplace.details.len();
+    workplace
+        .roles
+        .iter()
+        .map(|role| {
+            let heading = row;
+            row += role.details.len() + 1;
+            heading
+        })
+        .collect()
 }
 
 impl<'a> Navigable for JobView<'a> {
@@ -134,13 +209,29 @@ impl<'a> Navigable for JobView<'a> {
         self.menu.decrement();
     }
 
+    fn select_item(&mut self, idx: usize) {
+        if let Some(detail) = self.detail.as_mut() {
+            detail.select_item(idx);
+            return;
+        }
+        self.menu.select_item(idx);
+    }
+
     fn handle_enter(&mut self) {
         log::trace!("JobPage::handle_enter");
-        let Some(idx) = self.menu.selected() else {
-            log::warn!("menu selected returned None");
+        if let Some(detail) = self.detail.as_mut() {
+            detail.handle_enter();
+            return;
+        }
+        let Some(detail) = self.selected_detail() else {
+            log::warn!("no detail selected");
             return;
         };
-        self.detail = self.workplace.details.get(idx).map(Into::into);
+        self.detail = Some(DetailView::new(
+            detail.headline,
+            detail.detail,
+            self.context.clone(),
+        ));
     }
 
     fn handle_left(&mut self) -> bool {
@@ -149,6 +240,35 @@ impl<'a> Navigable for JobView<'a> {
         }
         false
     }
+
+    fn focus_next_link(&mut self) {
+        if let Some(detail) = self.detail.as_mut() {
+            detail.focus_next_link();
+        }
+    }
+
+    fn jump_to_heading(&mut self, forward: bool) {
+        if let Some(detail) = self.detail.as_mut() {
+            detail.jump_to_heading(forward);
+        }
+    }
+
+    fn fit_to_view(&mut self) {
+        if let Some(detail) = self.detail.as_mut() {
+            detail.fit_to_view();
+        }
+    }
+
+    fn take_action(&mut self) -> Option<Action> {
+        self.detail.as_mut()?.take_action()
+    }
+
+    fn yank(&self) -> Option<String> {
+        if let Some(detail) = self.detail.as_ref() {
+            return detail.yank();
+        }
+        Some(self.selected_detail()?.detail.to_string())
+    }
 }
 
 impl<'a> Widget for JobView<'a> {
@@ -167,21 +287,29 @@ impl<'a> Widget for JobView<'a> {
             header,
             buf,
             [
-                ("Company", self.workplace.name),
-                ("Title", self.workplace.title),
-                ("Start", self.workplace.start),
-                ("End", self.workplace.end.unwrap_or("Current")),
+                ("Company", self.workplace.name.to_string()),
+                ("Title", self.workplace.title.to_string()),
+                ("Start", self.workplace.start.to_string()),
+                (
+                    "End",
+                    self.workplace
+                        .end
+                        .map(|end| end.to_string())
+                        .unwrap_or_else(|| "Current".to_string()),
+                ),
             ]
             .into_iter(),
+            tenure(self.workplace.tenure(self.today)),
         );
-        render_job_details(&mut self.menu, self.workplace.details.iter(), details, buf);
+        render_job_details(&mut self.menu, &self.workplace, self.today, details, buf);
     }
 }
 
 fn render_header(
     area: Rect,
     buf: &mut Buffer,
-    details: impl Iterator<Item = (&'static str, &'static str)>,
+    details: impl Iterator<Item = (&'static str, String)>,
+    tenure: String,
 ) {
     let borders = [
         (
@@ -218,43 +346,69 @@ fn render_header(
         ),
     ];
     let cells: [Rect; 4] = Layout::horizontal(Constraint::from_percentages([25; 4])).areas(area);
-    for ((cell, (title, content)), (borders, set)) in cells
-        .into_iter()
-        .zip(details.into_iter())
-        .zip(borders.into_iter())
-    {
-        render_header_block(cell, buf, title, content, borders, set);
+    let blocks = cells.into_iter().zip(details).zip(borders).enumerate();
+    for (idx, ((cell, (title, content)), (borders, set))) in blocks {
+        let mut block = Block::bordered()
+            .title(title)
+            .title_alignment(Alignment::Left);
+        // the time at the job is displayed next to the end date
+        if idx == cells.len() - 1 {
+            block = block.title(Line::from(tenure.as_str()).right_aligned());
+        }
+        render_header_block(cell, buf, block, content, borders, set);
     }
 }
 
 fn render_header_block(
     area: Rect,
     buf: &mut Buffer,
-    title: &'static str,
-    content: &'static str,
+    block: Block,
+    content: String,
     border: Borders,
     corners: Set,
 ) {
-    let block = Block::bordered()
-        .title(title)
-        .title_alignment(Alignment::Left)
+    let block = block
         .style(DEFAULT_STYLE)
         .borders(border)
         .border_set(corners)
         .border_style(DEFAULT_STYLE);
     let rect = block.inner(area);
     block.render(area, buf);
-    let content = convert_md(content);
+    let content = convert_md(&content);
     Paragraph::new(content).render(rect, buf);
 }
 
-fn render_job_details<'a>(
+/// The details that aren't tied to a role followed by each role as a heading
+/// with its details indented below it
+fn render_job_details(
     state: &mut ListState,
-    details: impl Iterator<Item = &'a Detail>,
+    workplace: &Workplace,
+    today: Date,
     area: Rect,
     buf: &mut Buffer,
 ) {
-    let list: Vec<_> = details.into_iter().map(map_detail_to_list_item).collect();
+    let mut list: Vec<_> = workplace
+        .details
+        .iter()
+        .map(map_detail_to_list_item)
+        .collect();
+    for role in workplace.roles {
+        let dates = date_range(role.start, role.end);
+        let tenure = tenure(role.tenure(today));
+        list.push(ListItem::new(Text::from(vec![
+            Line::from(
+                role.title
+                    .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            ),
+            Line::from(format!("  {dates} ({tenure})")),
+        ])));
+        for detail in role.details {
+            list.push(ListItem::new(Text::from(vec![
+                Line::from(format!("  {}", detail.headline).add_modifier(Modifier::BOLD)),
+                Line::from(format!("    {}", detail.snippet)),
+            ])));
+        }
+    }
     StatefulWidget::render(
         List::new(list).highlight_style(Style::new().bg(Color::Green).fg(Color::Black)),
         area,
@@ -263,9 +417,164 @@ fn render_job_details<'a>(
     );
 }
 
-fn map_detail_to_list_item(detail: &Detail) -> ListItem {
-    let title = Line::from(detail.headline.add_modifier(Modifier::BOLD));
-    let details = Line::from(format!("  {}", detail.snippet));
-    let text = Text::from(vec![title, details]);
-    ListItem::new(text)
+fn map_detail_to_list_item<'a>(detail: &Detail<'a>) -> ListItem<'a> {
+    headline_list_item(detail.headline, detail.snippet, None)
+}
+
+/// A list item with a bold headline followed by an indented snippet and
+/// optionally a date range
+pub(crate) fn headline_list_item(
+    headline: &str,
+    snippet: impl Display,
+    dates: Option<String>,
+) -> ListItem<'_> {
+    let title = Line::from(headline.add_modifier(Modifier::BOLD));
+    let details = Line::from(format!("  {snippet}"));
+    let mut lines = vec![title, details];
+    if let Some(dates) = dates {
+        lines.push(Line::from(format!("    {dates}")));
+    }
+    ListItem::new(Text::from(lines))
+}
+
+/// Format a number of months like `3 yrs 7 mos`
+pub(crate) fn tenure(months: i32) -> String {
+    let plural = |n: i32, unit: &str| {
+        if n == 1 {
+            format!("{n} {unit}")
+        } else {
+            format!("{n} {unit}s")
+        }
+    };
+    let (years, months) = (months / 12, months % 12);
+    match (years, months) {
+        (0, months) => plural(months, "mo"),
+        (years, 0) => plural(years, "yr"),
+        (years, months) => format!("{} {}", plural(years, "yr"), plural(months, "mo")),
+    }
+}
+
+/// Format a start and optional end date, a missing end date is displayed as
+/// "Current"
+pub(crate) fn date_range<T: Display>(start: T, end: Option<T>) -> String {
+    match end {
+        Some(end) => format!("{start} - {end}"),
+        None => format!("{start} - Current"),
+    }
+}
+
+impl<'a> Page for WorkView<'a> {
+    fn title(&self) -> &str {
+        "Work"
+    }
+
+    fn id(&self) -> &str {
+        "work"
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        self.fit_to_view();
+        Widget::render(self.clone(), area, buf);
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(self.jobs, self.context.clone());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ratatui::{backend::TestBackend, Terminal};
+
+    use super::*;
+    use crate::data::Role;
+
+    const fn detail(headline: &'static str) -> Detail<'static> {
+        Detail {
+            id: 0,
+            headline,
+            snippet: "snippet",
+            detail: headline,
+            tags: &[],
+        }
+    }
+
+    /// Two roles with the same title around a promotion
+    static ROLES: &[Role] = &[
+        Role {
+            title: "Engineer",
+            start: Date::new(2022, 7),
+            end: None,
+            details: &[detail("Third"), detail("Fourth")],
+        },
+        Role {
+            title: "Lead",
+            start: Date::new(2021, 1),
+            end: Some(Date::new(2022, 6)),
+            details: &[],
+        },
+        Role {
+            title: "Engineer",
+            start: Date::new(2020, 3),
+            end: Some(Date::new(2020, 12)),
+            details: &[detail("Fifth")],
+        },
+    ];
+
+    static DETAILS: &[Detail] = &[detail("First"), detail("Second")];
+
+    fn job() -> Workplace<'static> {
+        Workplace {
+            name: "Acme",
+            title: "Engineer",
+            start: Date::new(2020, 3),
+            end: None,
+            details: DETAILS,
+            roles: ROLES,
+        }
+    }
+
+    #[test]
+    fn select_details_across_roles() {
+        let mut view = JobView::new(job(), Context::default());
+        let mut selected = vec![view.yank()];
+        for _ in 0..5 {
+            view.increment_selection();
+            selected.push(view.yank());
+        }
+        let expected = ["First", "Second", "Third", "Fourth", "Fifth", "Fifth"];
+        assert_eq!(selected, expected.map(|s| Some(s.to_string())));
+        for _ in 0..3 {
+            view.decrement_selection();
+        }
+        assert_eq!(view.yank().as_deref(), Some("Second"));
+        view.increment_selection();
+        view.handle_enter();
+        assert_eq!(
+            view.detail.as_ref().map(|d| d.yank()),
+            Some(Some("Third".to_string()))
+        );
+    }
+
+    #[test]
+    fn render_roles() {
+        let mut view = JobView::new(job(), Context::default());
+        view.today = Date::new(2024, 2);
+        view.increment_selection();
+        view.increment_selection();
+        let mut term = Terminal::new(TestBackend::new(60, 22)).unwrap();
+        term.draw(|f| f.render_widget(view.clone(), f.area()))
+            .unwrap();
+        insta::assert_snapshot!(term.backend());
+    }
+
+    #[test]
+    fn format_tenure() {
+        assert_eq!(tenure(0), "0 mos");
+        assert_eq!(tenure(1), "1 mo");
+        assert_eq!(tenure(11), "11 mos");
+        assert_eq!(tenure(12), "1 yr");
+        assert_eq!(tenure(13), "1 yr 1 mo");
+        assert_eq!(tenure(26), "2 yrs 2 mos");
+    }
 }